@@ -0,0 +1,235 @@
+//! Batch benchmark harness: plays many games with a bot on given board options,
+//! through the pure rule engine, and reports the win rate, the mean time per game,
+//! the fraction of boards requiring guesses and the 3BV distribution.
+//!
+//! ```text
+//! cargo run --release -p board_plugin --bin benchmark -- [OPTIONS]
+//!
+//!   --games <N>           number of games to play (default: 1000)
+//!   --options <FILE>      JSON board options (default: `BoardOptions::default()`)
+//!   --width <W>           overrides the map width
+//!   --height <H>          overrides the map height
+//!   --mines <M>           overrides the mine count
+//!   --lives <L>           overrides the lives count
+//!   --bot <solver|random> bot strategy (default: solver)
+//!   --seed <S>            base seed, game `i` uses `S + i` (default: random)
+//!   --format <json|csv>   JSON summary, or CSV with one row per game (default: json)
+//! ```
+
+use std::{collections::BTreeMap, fs, process, time::Instant};
+
+use board_plugin::{
+    bot::{Bot, RandomBot, SolverBot},
+    engine::Game,
+    resource::BoardOptions,
+};
+use rand::{Rng, SeedableRng};
+use rand_chacha::ChaCha8Rng;
+use serde::Serialize;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum BotKind {
+    Solver,
+    Random,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Format {
+    Json,
+    Csv,
+}
+
+#[derive(Debug, Clone)]
+struct Args {
+    games: u32,
+    options: BoardOptions,
+    bot: BotKind,
+    seed: u64,
+    format: Format,
+}
+
+/// Result of a single game
+#[derive(Debug, Clone, Serialize)]
+struct GameRecord {
+    seed: u64,
+    won: bool,
+    mines_hit: u16,
+    /// Guesses made by the solver bot, `None` for the random bot
+    guesses: Option<u32>,
+    bbbv: u32,
+    actions: u32,
+    time_ms: f64,
+}
+
+/// Aggregated results of every game
+#[derive(Debug, Clone, Serialize)]
+struct Summary {
+    games: u32,
+    wins: u32,
+    win_rate: f64,
+    mean_time_ms: f64,
+    /// Fraction of boards where the solver bot had to guess, `None` for the random bot
+    guess_fraction: Option<f64>,
+    mean_bbbv: f64,
+    /// Number of boards for each 3BV value
+    bbbv_distribution: BTreeMap<u32, u32>,
+}
+
+fn main() {
+    let args = match parse_args(std::env::args().skip(1)) {
+        Ok(args) => args,
+        Err(e) => {
+            eprintln!("error: {e}");
+            process::exit(2);
+        }
+    };
+    let records: Vec<GameRecord> = (0..args.games)
+        .map(|i| play_game(&args, args.seed.wrapping_add(i as u64)))
+        .collect();
+    let summary = summarize(&records);
+    eprintln!(
+        "{} games: {:.1}% won, {:.3} ms per game, mean 3BV {:.1}",
+        summary.games,
+        summary.win_rate * 100.,
+        summary.mean_time_ms,
+        summary.mean_bbbv
+    );
+
+    match args.format {
+        Format::Json => match serde_json::to_string_pretty(&summary) {
+            Ok(json) => println!("{json}"),
+            Err(e) => {
+                eprintln!("error: {e}");
+                process::exit(1);
+            }
+        },
+        Format::Csv => {
+            println!("seed,won,mines_hit,guesses,bbbv,actions,time_ms");
+            for r in records.iter() {
+                let guesses = r.guesses.map(|g| g.to_string()).unwrap_or_default();
+                println!(
+                    "{},{},{},{},{},{},{:.3}",
+                    r.seed, r.won, r.mines_hit, guesses, r.bbbv, r.actions, r.time_ms
+                );
+            }
+        }
+    }
+}
+
+fn play_game(args: &Args, seed: u64) -> GameRecord {
+    let start = Instant::now();
+    // Same generator as the game, so a seed plays the board the game generates for it
+    let mut rng = ChaCha8Rng::seed_from_u64(seed);
+    let mut game = Game::from_options(&args.options, &mut rng);
+    let bbbv = game.tile_map().bbbv();
+    let (actions, guesses) = match args.bot {
+        BotKind::Solver => {
+            let mut bot = SolverBot::new();
+            let actions = run_bot(&mut game, &mut bot);
+            (actions, Some(bot.guesses()))
+        }
+        BotKind::Random => (run_bot(&mut game, &mut RandomBot::new(Some(seed))), None),
+    };
+    GameRecord {
+        seed,
+        won: game.stats().outcome.is_some_and(|o| o.is_victory()),
+        mines_hit: game.stats().mines_hit,
+        guesses,
+        bbbv,
+        actions,
+        time_ms: start.elapsed().as_secs_f64() * 1000.,
+    }
+}
+
+/// Lets the bot play until the game is finished, returning the number of actions played
+fn run_bot(game: &mut Game, bot: &mut impl Bot) -> u32 {
+    // Every useful action changes the board, so a stuck bot is stopped
+    let max_actions = game.tile_map().tile_count() as u32 * 4 + 16;
+    let mut actions = 0;
+    while !game.stats().is_finished() && actions < max_actions {
+        let Some(action) = bot.next_action(&game.view()) else {
+            break;
+        };
+        game.play(action);
+        actions += 1;
+    }
+    actions
+}
+
+fn summarize(records: &[GameRecord]) -> Summary {
+    let games = records.len() as u32;
+    let count = games.max(1) as f64;
+    let wins = records.iter().filter(|r| r.won).count() as u32;
+    let guessed: Option<Vec<u32>> = records.iter().map(|r| r.guesses).collect();
+    let mut bbbv_distribution = BTreeMap::new();
+    for r in records.iter() {
+        *bbbv_distribution.entry(r.bbbv).or_default() += 1;
+    }
+    Summary {
+        games,
+        wins,
+        win_rate: wins as f64 / count,
+        mean_time_ms: records.iter().map(|r| r.time_ms).sum::<f64>() / count,
+        guess_fraction: guessed
+            .map(|guesses| guesses.iter().filter(|g| **g > 0).count() as f64 / count),
+        mean_bbbv: records.iter().map(|r| r.bbbv as f64).sum::<f64>() / count,
+        bbbv_distribution,
+    }
+}
+
+fn parse_args(mut args: impl Iterator<Item = String>) -> Result<Args, String> {
+    let mut res = Args {
+        games: 1000,
+        options: BoardOptions::default(),
+        bot: BotKind::Solver,
+        seed: rand::thread_rng().gen(),
+        format: Format::Json,
+    };
+    let mut overrides: Vec<(String, String)> = Vec::new();
+    while let Some(arg) = args.next() {
+        let value = args
+            .next()
+            .ok_or_else(|| format!("missing value for `{arg}`"))?;
+        match arg.as_str() {
+            "--games" => res.games = parse(&arg, &value)?,
+            "--options" => {
+                let content = fs::read_to_string(&value).map_err(|e| format!("{value}: {e}"))?;
+                res.options =
+                    serde_json::from_str(&content).map_err(|e| format!("{value}: {e}"))?;
+            }
+            "--width" | "--height" | "--mines" | "--lives" => overrides.push((arg, value)),
+            "--bot" => {
+                res.bot = match value.as_str() {
+                    "solver" => BotKind::Solver,
+                    "random" => BotKind::Random,
+                    _ => return Err(format!("unknown bot `{value}`")),
+                }
+            }
+            "--seed" => res.seed = parse(&arg, &value)?,
+            "--format" => {
+                res.format = match value.as_str() {
+                    "json" => Format::Json,
+                    "csv" => Format::Csv,
+                    _ => return Err(format!("unknown format `{value}`")),
+                }
+            }
+            _ => return Err(format!("unknown argument `{arg}`")),
+        }
+    }
+    // Overrides apply on top of the options file, whatever the argument order
+    for (arg, value) in overrides {
+        match arg.as_str() {
+            "--width" => res.options.map_size.0 = parse(&arg, &value)?,
+            "--height" => res.options.map_size.1 = parse(&arg, &value)?,
+            "--mines" => res.options.bomb_count = parse(&arg, &value)?,
+            _ => res.options.lives = parse(&arg, &value)?,
+        }
+    }
+    Ok(res)
+}
+
+fn parse<T: std::str::FromStr>(arg: &str, value: &str) -> Result<T, String> {
+    value
+        .parse()
+        .map_err(|_| format!("invalid value `{value}` for `{arg}`"))
+}
@@ -0,0 +1,7 @@
+mod random_bot;
+mod solver_bot;
+mod strategy;
+
+pub use random_bot::*;
+pub use solver_bot::*;
+pub use strategy::*;
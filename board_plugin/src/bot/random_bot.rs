@@ -0,0 +1,39 @@
+use rand::{seq::IteratorRandom, SeedableRng};
+use rand_chacha::ChaCha8Rng;
+
+use crate::solver::{BoardView, TileView};
+
+use super::{Bot, BotAction};
+
+/// Bot revealing random covered tiles
+#[derive(Debug, Clone)]
+pub struct RandomBot {
+    rng: ChaCha8Rng,
+}
+
+impl RandomBot {
+    /// Creates a random bot, `seed` making its choices reproducible across platforms and
+    /// dependency updates
+    pub fn new(seed: Option<u64>) -> Self {
+        let rng = match seed {
+            Some(seed) => ChaCha8Rng::seed_from_u64(seed),
+            None => ChaCha8Rng::from_entropy(),
+        };
+        Self { rng }
+    }
+}
+
+impl Default for RandomBot {
+    fn default() -> Self {
+        Self::new(None)
+    }
+}
+
+impl Bot for RandomBot {
+    fn next_action(&mut self, view: &BoardView) -> Option<BotAction> {
+        view.coordinates()
+            .filter(|c| view.get(*c) == TileView::Covered)
+            .choose(&mut self.rng)
+            .map(BotAction::Reveal)
+    }
+}
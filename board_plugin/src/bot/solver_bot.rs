@@ -0,0 +1,83 @@
+use crate::{
+    component::Coordinates,
+    solver::{BoardView, Deductions, MineProbabilities, TileView},
+};
+
+use super::{Bot, BotAction};
+
+/// Deterministic bot playing the solver deductions, and guessing the lowest risk tile
+/// when no deduction exists.
+///
+/// Flags and chords are only used on single mine boards
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct SolverBot {
+    guesses: u32,
+}
+
+impl SolverBot {
+    /// Creates a solver bot
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Number of guesses the bot had to make
+    pub fn guesses(&self) -> u32 {
+        self.guesses
+    }
+
+    /// Finds a revealed number whose neighbor flags are all deduced mines matching its count,
+    /// with covered neighbors left to uncover
+    fn chord_target(view: &BoardView, deductions: &Deductions) -> Option<Coordinates> {
+        view.coordinates().find(|coords| {
+            let TileView::Revealed(count) = view.get(*coords) else {
+                return false;
+            };
+            let mut flags = 0;
+            let mut covered = false;
+            for neighbor in view.neighbors(*coords) {
+                match view.get(neighbor) {
+                    TileView::Flagged(n) if deductions.mines.contains(&neighbor) => {
+                        flags += n as u16
+                    }
+                    // Wrong or unproven flags make chording unsafe
+                    TileView::Flagged(_) => return false,
+                    TileView::Mine(n) => flags += n as u16,
+                    TileView::Covered => covered = true,
+                    _ => (),
+                }
+            }
+            count > 0 && covered && flags == count as u16
+        })
+    }
+}
+
+impl Bot for SolverBot {
+    fn next_action(&mut self, view: &BoardView) -> Option<BotAction> {
+        let deductions = Deductions::solve(view);
+        let single_mines = view.max_stack() == 1;
+        if single_mines {
+            if let Some(coords) = Self::chord_target(view, &deductions) {
+                return Some(BotAction::Chord(coords));
+            }
+        }
+        let covered = |coords: &&Coordinates| view.get(**coords) == TileView::Covered;
+        if let Some(coords) = deductions.safe.iter().find(covered) {
+            return Some(BotAction::Reveal(*coords));
+        }
+        if single_mines {
+            if let Some(coords) = deductions.mines.iter().find(covered) {
+                return Some(BotAction::Flag(*coords));
+            }
+        }
+        // No simple deduction left, we pick the lowest risk tile which is
+        // only a guess if the enumeration didn't prove it safe
+        let (coords, p) = MineProbabilities::compute(view)
+            .iter()
+            .filter(|(coords, _)| view.get(*coords) == TileView::Covered)
+            .min_by(|(_, a), (_, b)| a.total_cmp(b))?;
+        if p > 0. {
+            self.guesses += 1;
+        }
+        Some(BotAction::Reveal(coords))
+    }
+}
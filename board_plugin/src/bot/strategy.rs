@@ -0,0 +1,18 @@
+use crate::{component::Coordinates, solver::BoardView};
+
+/// Action a bot can play on a tile
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum BotAction {
+    /// Uncovers a covered tile
+    Reveal(Coordinates),
+    /// Toggles the flags of a covered tile
+    Flag(Coordinates),
+    /// Uncovers the neighbors of a revealed number whose flags match its count
+    Chord(Coordinates),
+}
+
+/// Pluggable bot strategy, only seeing the public board view
+pub trait Bot: Send + Sync {
+    /// Picks the next action to play, `None` if the bot has nothing to play
+    fn next_action(&mut self, view: &BoardView) -> Option<BotAction>;
+}
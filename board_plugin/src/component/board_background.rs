@@ -0,0 +1,5 @@
+use bevy::prelude::Component;
+
+/// Board background sprite marker component
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Component, Default)]
+pub struct BoardBackground;
@@ -1,4 +1,5 @@
 use bevy::prelude::Component;
+use derive_more::From;
 
 #[cfg(feature = "debug")]
 use bevy::prelude::Reflect;
@@ -6,10 +7,13 @@ use bevy::prelude::Reflect;
 use bevy_inspector_egui::prelude::*;
 
 /// Bomb component
-#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Component, Default)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Component, Default, From)]
 #[cfg_attr(
     feature = "debug",
     derive(InspectorOptions, Reflect),
     reflect(InspectorOptions)
 )]
-pub struct Bomb;
+pub struct Bomb {
+    /// Number of stacked mines
+    pub count: u8,
+}
@@ -14,6 +14,6 @@ use bevy_inspector_egui::prelude::*;
     reflect(InspectorOptions)
 )]
 pub struct BombNeighbor {
-    /// Number of neighbor bombs
+    /// Number of neighbor mines, summed over the neighbor stacks
     pub count: u8,
 }
@@ -0,0 +1,24 @@
+use bevy::prelude::Component;
+use serde::{Deserialize, Serialize};
+
+/// Spawned board chunk component, its children are the chunk tiles
+#[derive(
+    Debug,
+    Clone,
+    Copy,
+    PartialEq,
+    Eq,
+    PartialOrd,
+    Ord,
+    Hash,
+    Component,
+    Default,
+    Serialize,
+    Deserialize,
+)]
+pub struct Chunk {
+    /// Chunk column
+    pub x: i32,
+    /// Chunk row
+    pub y: i32,
+}
@@ -1,5 +1,6 @@
 use bevy::prelude::Component;
 use derive_more::{Add, Display, From, Sub};
+use serde::{Deserialize, Serialize};
 
 #[cfg(feature = "debug")]
 use bevy::prelude::{Reflect, FromReflect};
@@ -21,6 +22,8 @@ use bevy_inspector_egui::prelude::*;
     Display,
     From,
     Default,
+    Serialize,
+    Deserialize,
 )]
 #[cfg_attr(
     feature = "debug",
@@ -29,6 +32,24 @@ use bevy_inspector_egui::prelude::*;
 )]
 #[display(fmt = "({}, {})", x, y)]
 pub struct Coordinates {
-    pub x: u16,
-    pub y: u16,
+    pub x: i32,
+    pub y: i32,
+}
+
+impl Coordinates {
+    /// Is the tile inside a `width` x `height` board, starting from the origin
+    pub fn is_within(&self, (width, height): (u16, u16)) -> bool {
+        (0..width as i32).contains(&self.x) && (0..height as i32).contains(&self.y)
+    }
+}
+
+impl std::ops::Add<(i8, i8)> for Coordinates {
+    type Output = Self;
+
+    fn add(self, (x, y): (i8, i8)) -> Self::Output {
+        Self {
+            x: self.x + x as i32,
+            y: self.y + y as i32,
+        }
+    }
 }
@@ -0,0 +1,5 @@
+use bevy::prelude::Component;
+
+/// Daily challenge date text marker component
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Component, Default)]
+pub struct DailyLabel;
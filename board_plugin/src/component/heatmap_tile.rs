@@ -0,0 +1,5 @@
+use bevy::prelude::Component;
+
+/// Heatmap overlay marker component, tinting a covered tile by its mine probability
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Component, Default)]
+pub struct HeatmapTile;
@@ -0,0 +1,5 @@
+use bevy::prelude::Component;
+
+/// Hint overlay marker component, displayed over the hinted tile cover
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Component, Default)]
+pub struct HintHighlight;
@@ -0,0 +1,5 @@
+use bevy::prelude::Component;
+
+/// Heads-up display text marker component
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Component, Default)]
+pub struct Hud;
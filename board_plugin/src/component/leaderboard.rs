@@ -0,0 +1,5 @@
+use bevy::prelude::Component;
+
+/// High score leaderboard screen marker component
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Component, Default)]
+pub struct Leaderboard;
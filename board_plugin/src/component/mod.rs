@@ -1,9 +1,31 @@
 mod coordinates;
+mod board_background;
 mod bomb;
 mod bomb_neighbor;
+mod chunk;
+mod daily_label;
+mod heatmap_tile;
+mod hint_highlight;
+mod hud;
+mod leaderboard;
+mod pressed_tile;
+mod stack_count;
+mod stats_screen;
+mod tile_cursor;
 mod uncover;
 
 pub use coordinates::Coordinates;
+pub use board_background::BoardBackground;
 pub use bomb::Bomb;
 pub use bomb_neighbor::BombNeighbor;
+pub use chunk::Chunk;
+pub use daily_label::DailyLabel;
+pub use heatmap_tile::HeatmapTile;
+pub use hint_highlight::HintHighlight;
+pub use hud::Hud;
+pub use leaderboard::Leaderboard;
+pub use pressed_tile::PressedTile;
+pub use stack_count::StackCount;
+pub use stats_screen::StatsScreen;
+pub use tile_cursor::TileCursor;
 pub use uncover::Uncover;
\ No newline at end of file
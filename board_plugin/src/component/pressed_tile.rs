@@ -0,0 +1,8 @@
+use bevy::prelude::{Color, Component};
+
+/// Tile cover depressed by a held mouse button, restored to its cover `color` on release
+#[derive(Debug, Clone, Copy, PartialEq, Component)]
+pub struct PressedTile {
+    /// Cover color before the press
+    pub color: Color,
+}
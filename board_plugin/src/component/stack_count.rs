@@ -0,0 +1,5 @@
+use bevy::prelude::Component;
+
+/// Stacked mines or flags count text marker component
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Component, Default)]
+pub struct StackCount;
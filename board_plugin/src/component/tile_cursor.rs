@@ -0,0 +1,31 @@
+use bevy::prelude::{Component, Vec3};
+
+use super::Coordinates;
+
+/// Keyboard and gamepad tile cursor component, hidden until it is first moved
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Component, Default)]
+pub struct TileCursor {
+    /// Coordinates of the selected tile
+    pub coordinates: Coordinates,
+}
+
+impl TileCursor {
+    /// Cursor translation relative to the board, centered on the selected tile
+    pub fn translation(&self, tile_size: f32) -> Vec3 {
+        Vec3::new(
+            self.coordinates.x as f32 * tile_size + tile_size / 2.,
+            self.coordinates.y as f32 * tile_size + tile_size / 2.,
+            10.,
+        )
+    }
+
+    /// Moves the cursor by `(dx, dy)`, clamped to a `width` x `height` board if it is bounded
+    pub fn shift(&mut self, (dx, dy): (i32, i32), size: Option<(u16, u16)>) {
+        self.coordinates.x += dx;
+        self.coordinates.y += dy;
+        if let Some((width, height)) = size {
+            self.coordinates.x = self.coordinates.x.clamp(0, width as i32 - 1);
+            self.coordinates.y = self.coordinates.y.clamp(0, height as i32 - 1);
+        }
+    }
+}
@@ -0,0 +1,366 @@
+use rand::Rng;
+
+use crate::{
+    bot::BotAction,
+    component::Coordinates,
+    resource::{BoardOptions, GameOutcome, GameStats, Tile, TileMap},
+    solver::{BoardView, TileView},
+};
+
+/// Pure Minesweeper rule engine, playing a tile map without any entity.
+///
+/// It follows the same rules as the board plugin systems, and is used to simulate games
+#[derive(Debug, Clone)]
+pub struct Game {
+    tile_map: TileMap,
+    covered: Vec<bool>,
+    flags: Vec<u8>,
+    exploded: Vec<bool>,
+    stats: GameStats,
+}
+
+impl Game {
+    /// Starts a game on `tile_map`, the player being able to step on `lives - 1` mines
+    pub fn new(tile_map: TileMap, lives: u8) -> Self {
+        let len = tile_map.width() as usize * tile_map.height() as usize;
+        let mut covered = vec![true; len];
+        for (y, line) in tile_map.iter().enumerate() {
+            for (x, tile) in line.iter().enumerate() {
+                if tile.is_disabled() {
+                    covered[y * tile_map.width() as usize + x] = false;
+                }
+            }
+        }
+        let bbbv = tile_map.bbbv();
+        Self {
+            tile_map,
+            covered,
+            flags: vec![0; len],
+            exploded: vec![false; len],
+            stats: GameStats::new(lives.max(1), bbbv),
+        }
+    }
+
+    /// Generates a board from the options and starts a game on it, uncovering
+    /// the safe start tile if required
+    pub fn from_options(options: &BoardOptions, rng: &mut impl Rng) -> Self {
+        let mut res = Self::new(TileMap::generate(options, rng), options.lives);
+        if options.safe_start {
+            res.uncover_safe_start();
+        }
+        res
+    }
+
+    /// Uncovers the safe start tile without counting a click, returning `true` if there was one
+    pub fn uncover_safe_start(&mut self) -> bool {
+        match self.safe_start() {
+            Some(coords) => self.uncover(coords),
+            None => false,
+        }
+    }
+
+    /// Getter for `tile_map`
+    pub fn tile_map(&self) -> &TileMap {
+        &self.tile_map
+    }
+
+    /// Getter for `stats`
+    pub fn stats(&self) -> &GameStats {
+        &self.stats
+    }
+
+    /// Is the tile at `coords` covered
+    pub fn is_covered(&self, coords: Coordinates) -> bool {
+        self.index(coords).is_some_and(|i| self.covered[i])
+    }
+
+    /// Flag count of the tile at `coords`, exploded mines are flagged too
+    pub fn flags(&self, coords: Coordinates) -> u8 {
+        self.index(coords).map_or(0, |i| self.flags[i])
+    }
+
+    /// Is the tile at `coords` a mine the player stepped on and survived
+    pub fn is_exploded(&self, coords: Coordinates) -> bool {
+        self.index(coords).is_some_and(|i| self.exploded[i])
+    }
+
+    /// Plays an action, returning `true` if it changed the board
+    pub fn play(&mut self, action: BotAction) -> bool {
+        match action {
+            BotAction::Reveal(coords) => self.reveal(coords),
+            BotAction::Flag(coords) => self.toggle_flag(coords),
+            BotAction::Chord(coords) => self.chord(coords),
+        }
+    }
+
+    /// Uncovers a covered and unmarked tile, propagating through empty tiles.
+    ///
+    /// Returns `true` if it changed the board
+    pub fn reveal(&mut self, coords: Coordinates) -> bool {
+        if self.stats.is_finished() || self.index(coords).is_none() {
+            return false;
+        }
+        self.stats.left_clicks += 1;
+        let changed = self.uncover(coords);
+        self.stats.effective_clicks += changed as u32;
+        changed
+    }
+
+    /// Uncovers a tile without counting a click
+    fn uncover(&mut self, coords: Coordinates) -> bool {
+        let Some(i) = self.index(coords) else {
+            return false;
+        };
+        if self.stats.is_finished() || !self.covered[i] || self.flags[i] > 0 {
+            return false;
+        }
+        if let Some(Tile::Bomb(count)) = self.tile(coords) {
+            self.stats.mines_hit += 1;
+            self.stats.lives_left = self.stats.lives_left.saturating_sub(1);
+            // While the player has lives left, the mine is flagged instead of uncovered
+            if self.stats.lives_left > 0 {
+                self.flags[i] = count;
+                self.exploded[i] = true;
+            } else {
+                self.covered[i] = false;
+                self.stats.outcome = Some(GameOutcome::Defeat);
+            }
+            return true;
+        }
+        let mut stack = vec![coords];
+        self.covered[i] = false;
+        self.stats.tiles_uncovered += 1;
+        while let Some(coords) = stack.pop() {
+            if self.tile(coords) != Some(Tile::Empty) {
+                continue;
+            }
+            for neighbor in self.tile_map.safe_square_at(coords) {
+                let Some(j) = self.index(neighbor) else {
+                    continue;
+                };
+                if self.covered[j] && self.flags[j] == 0 {
+                    self.covered[j] = false;
+                    self.stats.tiles_uncovered += 1;
+                    stack.push(neighbor);
+                }
+            }
+        }
+        self.check_completion();
+        true
+    }
+
+    /// Cycles the flag count of a covered tile, exploded tiles flags are locked.
+    ///
+    /// Returns `true` if it changed the board
+    pub fn toggle_flag(&mut self, coords: Coordinates) -> bool {
+        let Some(i) = self.index(coords) else {
+            return false;
+        };
+        if self.stats.is_finished() {
+            return false;
+        }
+        self.stats.right_clicks += 1;
+        if !self.covered[i] || self.exploded[i] {
+            return false;
+        }
+        self.stats.effective_clicks += 1;
+        let max_stack = self.tile_map.max_stack().max(1);
+        self.flags[i] = match self.flags[i] {
+            n if n < max_stack => n + 1,
+            _ => 0,
+        };
+        true
+    }
+
+    /// Uncovers the unmarked neighbors of a revealed number whose neighbor flags match its count.
+    ///
+    /// Returns `true` if it changed the board
+    pub fn chord(&mut self, coords: Coordinates) -> bool {
+        if self.stats.is_finished() || self.index(coords).is_none() {
+            return false;
+        }
+        self.stats.chord_clicks += 1;
+        if self.is_covered(coords) {
+            return false;
+        }
+        let Some(Tile::BombNeighbor(count)) = self.tile(coords) else {
+            return false;
+        };
+        let flags: u16 = self
+            .tile_map
+            .safe_square_at(coords)
+            .filter_map(|c| self.index(c))
+            .map(|i| self.flags[i] as u16)
+            .sum();
+        if flags != count as u16 {
+            return false;
+        }
+        let neighbors: Vec<Coordinates> = self.tile_map.safe_square_at(coords).collect();
+        let mut changed = false;
+        for neighbor in neighbors {
+            changed |= self.uncover(neighbor);
+        }
+        self.stats.effective_clicks += changed as u32;
+        changed
+    }
+
+    /// Builds the public view of the board, as seen by the player
+    pub fn view(&self) -> BoardView {
+        let mut view = BoardView::new(
+            self.tile_map.width(),
+            self.tile_map.height(),
+            self.tile_map.neighborhood().clone(),
+            self.tile_map.bomb_count(),
+            self.tile_map.max_stack(),
+        );
+        for coords in view.coordinates().collect::<Vec<_>>() {
+            let (Some(i), Some(tile)) = (self.index(coords), self.tile(coords)) else {
+                continue;
+            };
+            let tile = match tile {
+                Tile::Disabled => TileView::Disabled,
+                Tile::Bomb(n) if self.exploded[i] => TileView::Mine(n),
+                _ if self.covered[i] => match self.flags[i] {
+                    0 => TileView::Covered,
+                    n => TileView::Flagged(n),
+                },
+                Tile::Bomb(n) => TileView::Mine(n),
+                Tile::BombNeighbor(n) => TileView::Revealed(n),
+                Tile::Empty => TileView::Revealed(0),
+            };
+            view.set(coords, tile);
+        }
+        view
+    }
+
+    /// First empty tile, which the board plugin uncovers on safe starts
+    fn safe_start(&self) -> Option<Coordinates> {
+        let (width, height) = (self.tile_map.width(), self.tile_map.height());
+        (0..height as i32)
+            .flat_map(|y| (0..width as i32).map(move |x| Coordinates { x, y }))
+            .find(|c| self.tile(*c) == Some(Tile::Empty))
+    }
+
+    /// Declares the victory once every safe tile is uncovered
+    fn check_completion(&mut self) {
+        let covered = self.covered.iter().filter(|c| **c).count();
+        if !self.stats.is_finished() && covered == self.tile_map.mined_tile_count() {
+            self.stats.outcome = Some(GameOutcome::Victory);
+        }
+    }
+
+    /// Tile at `coords`, `None` out of the board
+    fn tile(&self, coords: Coordinates) -> Option<Tile> {
+        self.index(coords)
+            .map(|_| self.tile_map[coords.y as usize][coords.x as usize])
+    }
+
+    fn index(&self, Coordinates { x, y }: Coordinates) -> Option<usize> {
+        let size = (self.tile_map.width(), self.tile_map.height());
+        Coordinates { x, y }
+            .is_within(size)
+            .then(|| y as usize * self.tile_map.width() as usize + x as usize)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Game on a 3x3 map with mines on the top corners, the center tile being a 2
+    fn game(lives: u8) -> Game {
+        let mines = [Coordinates { x: 0, y: 2 }, Coordinates { x: 2, y: 2 }];
+        Game::new(TileMap::with_mines(3, 3, &mines), lives)
+    }
+
+    #[test]
+    fn reveal_propagates_and_wins() {
+        let mut game = game(1);
+        assert!(game.reveal(Coordinates { x: 1, y: 0 }));
+        // The opening stops at the numbers, the top 2 has no empty neighbor
+        assert_eq!(game.stats().tiles_uncovered, 6);
+        assert!(game.is_covered(Coordinates { x: 1, y: 2 }));
+        assert_eq!(game.stats().outcome, None);
+        assert!(game.reveal(Coordinates { x: 1, y: 2 }));
+        assert_eq!(game.stats().outcome, Some(GameOutcome::Victory));
+        assert!(!game.reveal(Coordinates { x: 0, y: 2 }));
+    }
+
+    #[test]
+    fn reveal_mine_loses() {
+        let mut game = game(1);
+        assert!(game.reveal(Coordinates { x: 0, y: 2 }));
+        assert_eq!(game.stats().outcome, Some(GameOutcome::Defeat));
+        assert!(!game.is_covered(Coordinates { x: 0, y: 2 }));
+        // The board is frozen once lost
+        assert!(!game.reveal(Coordinates { x: 1, y: 0 }));
+        assert!(!game.toggle_flag(Coordinates { x: 1, y: 0 }));
+    }
+
+    #[test]
+    fn extra_lives_flag_stepped_mines() {
+        let mut game = game(2);
+        assert!(game.reveal(Coordinates { x: 0, y: 2 }));
+        assert_eq!(game.stats().outcome, None);
+        assert_eq!(game.stats().lives_left, 1);
+        assert!(game.is_exploded(Coordinates { x: 0, y: 2 }));
+        assert_eq!(game.flags(Coordinates { x: 0, y: 2 }), 1);
+        // Exploded mines flags are locked
+        assert!(!game.toggle_flag(Coordinates { x: 0, y: 2 }));
+    }
+
+    #[test]
+    fn flags_protect_tiles() {
+        let mut game = game(1);
+        let coords = Coordinates { x: 0, y: 2 };
+        assert!(game.toggle_flag(coords));
+        assert_eq!(game.flags(coords), 1);
+        assert!(!game.reveal(coords));
+        assert!(game.toggle_flag(coords));
+        assert_eq!(game.flags(coords), 0);
+    }
+
+    #[test]
+    fn chord_needs_matching_flags() {
+        let mut game = game(1);
+        let center = Coordinates { x: 1, y: 1 };
+        assert!(game.reveal(center));
+        assert_eq!(game.stats().tiles_uncovered, 1);
+        assert!(!game.chord(center));
+        game.toggle_flag(Coordinates { x: 0, y: 2 });
+        assert!(!game.chord(center));
+        game.toggle_flag(Coordinates { x: 2, y: 2 });
+        assert!(game.chord(center));
+        assert_eq!(game.stats().outcome, Some(GameOutcome::Victory));
+    }
+
+    #[test]
+    fn chord_on_wrong_flags_loses() {
+        let mut game = game(1);
+        let center = Coordinates { x: 1, y: 1 };
+        game.reveal(center);
+        game.toggle_flag(Coordinates { x: 0, y: 2 });
+        game.toggle_flag(Coordinates { x: 1, y: 2 });
+        assert!(game.chord(center));
+        assert_eq!(game.stats().outcome, Some(GameOutcome::Defeat));
+    }
+
+    #[test]
+    fn out_of_board_coordinates_are_ignored() {
+        let mut game = game(1);
+        for coords in [
+            Coordinates { x: -1, y: 0 },
+            Coordinates { x: 0, y: -1 },
+            Coordinates { x: 3, y: 0 },
+            Coordinates { x: 0, y: 3 },
+        ] {
+            assert!(!game.reveal(coords));
+            assert!(!game.toggle_flag(coords));
+            assert!(!game.chord(coords));
+            assert!(!game.is_covered(coords));
+            assert_eq!(game.flags(coords), 0);
+        }
+        assert_eq!(game.stats().clicks(), 0);
+        assert_eq!(game.stats().outcome, None);
+    }
+}
@@ -1,6 +1,34 @@
 use derive_more::From;
 
-use crate::component::Coordinates;
+use crate::{component::Coordinates, resource::GameStats};
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default, From)]
 pub struct TileTriggerEvent(pub Coordinates);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default, From)]
+pub struct TileMarkEvent(pub Coordinates);
+
+/// Uncovers the unmarked neighbors of a revealed number whose flags match its count
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default, From)]
+pub struct TileChordEvent(pub Coordinates);
+
+/// Asks the solver for a hint on the current board
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+pub struct HintRequestEvent;
+
+/// Sent when the player steps on a mine
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+pub struct BombExplosionEvent {
+    /// Coordinates of the exploded tile
+    pub coordinates: Coordinates,
+    /// Lives left after the explosion
+    pub lives_left: u8,
+}
+
+/// Sent when every safe tile is uncovered, holding the game results
+#[derive(Debug, Clone, Default)]
+pub struct BoardCompletedEvent(pub GameStats);
+
+/// Sent when the player runs out of lives, holding the game results
+#[derive(Debug, Clone, Default)]
+pub struct GameOverEvent(pub GameStats);
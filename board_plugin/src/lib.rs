@@ -1,22 +1,50 @@
+pub mod bot;
 pub mod component;
-mod event;
+pub mod engine;
+pub mod event;
 pub mod resource;
+pub mod solver;
 mod system;
 
 use bevy::{
-    ecs::schedule::StateData, log, math::Vec3Swizzles, prelude::*, sprite::Anchor, utils::HashMap,
+    ecs::schedule::{RunCriteriaLabel, ShouldRun, StateData},
+    log,
+    math::Vec3Swizzles,
+    prelude::*,
+    sprite::Anchor,
+    utils::{HashMap, HashSet},
 };
 
-use component::{Bomb, BombNeighbor, Coordinates, Uncover};
-use event::TileTriggerEvent;
-use resource::{Board, BoardOptions, BoardPosition, Tile, TileMap, TileSize};
+use rand::SeedableRng;
+use rand_chacha::ChaCha8Rng;
 
-use crate::resource::LoadedAssets;
+use component::{
+    BoardBackground, Bomb, BombNeighbor, Coordinates, DailyLabel, Hud, Leaderboard, StackCount,
+    StatsScreen, TileCursor, Uncover,
+};
+use event::{
+    BoardCompletedEvent, BombExplosionEvent, GameOverEvent, HintRequestEvent, TileChordEvent,
+    TileMarkEvent, TileTriggerEvent,
+};
+use resource::{
+    Board, BoardOptions, BoardPosition, BoardTheme, ChunkMap, DailyChallenge, DailyResults,
+    EndlessMode, EndlessWorld, GameStats, Heatmap, HighScores, InputBindings, PlayerStats, Tile,
+    TileAtlas, TileMap, TilePress, TileRendering, TileSize,
+};
+
+use crate::{engine::Game, resource::LoadedAssets};
 
 pub struct BoardPlugin<T> {
     pub running_state: T,
 }
 
+/// Running state criteria, shared by the system sets of the different board renderings
+#[derive(Debug, Clone, PartialEq, Eq, Hash, RunCriteriaLabel)]
+enum BoardRunCriteria {
+    Update,
+    InStackUpdate,
+}
+
 impl<T: StateData> Plugin for BoardPlugin<T> {
     fn build(&self, app: &mut App) {
         #[cfg(feature = "debug")]
@@ -27,54 +55,196 @@ impl<T: StateData> Plugin for BoardPlugin<T> {
             .register_type::<TileMap>()
             .register_type::<Board>()
             .register_type::<BoardOptions>()
+            .register_type::<GameStats>()
+            .register_type::<Heatmap>()
             .register_type::<TileSize>();
 
         app.add_system_set(
             SystemSet::on_enter(self.running_state.clone()).with_system(Self::create_board),
         )
         .add_system_set(
-            SystemSet::on_update(self.running_state.clone())
+            SystemSet::new()
+                .with_run_criteria(
+                    State::on_update(self.running_state.clone()).label(BoardRunCriteria::Update),
+                )
                 .with_system(system::input::input_handling)
-                .with_system(system::uncover::trigger_event_handler),
+                .with_system(system::input::cursor_input_handling)
+                .with_system(system::gamepad::gamepad_input_handling)
+                .with_system(system::touch::touch_input_handling)
+                .with_system(system::input::hint_input_handling)
+                .with_system(system::input::heatmap_input_handling)
+                .with_system(system::theme::theme_input_handling)
+                .with_system(system::stats::tick_game_timer)
+                .with_system(system::high_scores::leaderboard_input_handling)
+                .with_system(system::player_stats::stats_screen_input_handling),
+        )
+        // Boards with tile entities
+        .add_system_set(
+            SystemSet::new()
+                .with_run_criteria(RunCriteria::pipe(BoardRunCriteria::Update, tile_entities))
+                .with_system(
+                    system::press::update_pressed_tiles.after(system::input::input_handling),
+                )
+                .with_system(system::uncover::trigger_event_handler)
+                .with_system(system::uncover::chord_event_handler)
+                .with_system(system::mark::mark_tiles)
+                .with_system(system::hint::hint_request_handler)
+                .with_system(system::bot::bot_play),
+        )
+        // Boards rendered in chunks
+        .add_system_set(
+            SystemSet::new()
+                .with_run_criteria(RunCriteria::pipe(BoardRunCriteria::Update, chunks))
+                .with_system(system::chunk::chunked_play)
+                .with_system(system::chunk::update_chunks.after(system::chunk::chunked_play)),
+        )
+        // Endless world
+        .add_system_set(
+            SystemSet::new()
+                .with_run_criteria(RunCriteria::pipe(BoardRunCriteria::Update, endless))
+                .with_system(system::endless::endless_play)
+                .with_system(
+                    system::endless::update_endless_chunks.after(system::endless::endless_play),
+                )
+                .with_system(
+                    system::endless::follow_tile_cursor
+                        .after(system::input::cursor_input_handling)
+                        .after(system::gamepad::gamepad_input_handling),
+                )
+                .with_system(system::endless::autosave_endless_world),
+        )
+        .add_system_set(
+            SystemSet::new()
+                .with_run_criteria(
+                    State::on_in_stack_update(self.running_state.clone())
+                        .label(BoardRunCriteria::InStackUpdate),
+                )
+                .with_system(system::hud::update_hud)
+                .with_system(system::theme::apply_theme)
+                .with_system(
+                    system::high_scores::record_high_score
+                        .after(system::uncover::uncover_tiles)
+                        .after(system::chunk::chunked_play)
+                        .after(system::endless::endless_play),
+                )
+                .with_system(
+                    system::player_stats::record_player_stats
+                        .after(system::uncover::uncover_tiles)
+                        .after(system::chunk::chunked_play)
+                        .after(system::endless::endless_play),
+                )
+                .with_system(
+                    system::daily::record_daily_result
+                        .after(system::uncover::uncover_tiles)
+                        .after(system::chunk::chunked_play)
+                        .after(system::endless::endless_play),
+                ),
+        )
+        .add_system_set(
+            SystemSet::new()
+                .with_run_criteria(RunCriteria::pipe(
+                    BoardRunCriteria::InStackUpdate,
+                    tile_entities,
+                ))
+                .with_system(system::uncover::uncover_tiles)
+                .with_system(system::heatmap::update_heatmap.after(system::uncover::uncover_tiles))
+                .with_system(system::game_over::reveal_mines.after(system::uncover::uncover_tiles)),
+        )
+        .add_system_set(
+            SystemSet::on_pause(self.running_state.clone()).with_system(Self::hide_board),
         )
         .add_system_set(
-            SystemSet::on_in_stack_update(self.running_state.clone())
-                .with_system(system::uncover::uncover_tiles),
+            SystemSet::on_resume(self.running_state.clone()).with_system(Self::show_board),
         )
         .add_system_set(
-            SystemSet::on_exit(self.running_state.clone()).with_system(Self::cleanup_board),
+            SystemSet::on_exit(self.running_state.clone())
+                .with_system(Self::cleanup_board)
+                .with_system(system::touch::reset_camera),
         )
-        .add_event::<TileTriggerEvent>();
+        .init_resource::<Heatmap>()
+        .init_resource::<BoardTheme>()
+        .insert_resource(HighScores::load())
+        .insert_resource(PlayerStats::load())
+        .insert_resource(DailyResults::load())
+        .insert_resource(InputBindings::load())
+        .add_event::<TileTriggerEvent>()
+        .add_event::<TileMarkEvent>()
+        .add_event::<TileChordEvent>()
+        .add_event::<HintRequestEvent>()
+        .add_event::<BombExplosionEvent>()
+        .add_event::<BoardCompletedEvent>()
+        .add_event::<GameOverEvent>();
         log::info!("Loaded Board Plugin");
     }
 }
 
 impl<T> BoardPlugin<T> {
     /// System to generate the complete board
+    #[allow(clippy::too_many_arguments)]
     pub fn create_board(
         mut commands: Commands,
         board_options: Option<Res<BoardOptions>>,
+        mut daily: Option<ResMut<DailyChallenge>>,
+        daily_results: Res<DailyResults>,
+        endless: Option<Res<EndlessMode>>,
+        theme: Res<BoardTheme>,
         windows: Res<Windows>,
         asset_server: Res<AssetServer>,
+        mut images: ResMut<Assets<Image>>,
+        mut atlases: ResMut<Assets<TextureAtlas>>,
+        mut cameras: Query<&mut Transform, With<Camera2d>>,
     ) {
-        let board_options = match board_options {
+        let mut board_options = match board_options {
             Some(o) => o.clone(),
             None => Default::default(),
         };
-        let font: Handle<Font> = asset_server.load("fonts/pixeled.ttf");
-        let bomb_image: Handle<Image> = asset_server.load("sprites/bomb.png");
+        // The daily challenge overrides the gameplay options, replays are unofficial
+        if let Some(daily) = daily.as_mut() {
+            daily.official = daily_results.get(daily.date).is_none();
+            board_options = daily.board_options(&board_options);
+            log::info!(
+                "Daily challenge {}{}",
+                daily.date,
+                if daily.official { "" } else { " (unofficial)" }
+            );
+        }
+        let loaded_assets = LoadedAssets::load(&theme, &asset_server);
+        log::debug!("Loaded assets: {loaded_assets:?}");
 
-        log::debug!("Font: {font:?}");
-        log::debug!("Bomb image: {bomb_image:?}");
+        // The endless world has no tile map, its chunks are generated as the player explores it
+        let endless_world = endless.map(|mode| {
+            EndlessWorld::load_or_new(
+                &mode,
+                board_options.seed,
+                &board_options.neighborhood,
+                board_options.tile_padding,
+            )
+        });
+        let unbounded = endless_world.is_some();
 
-        // Tilemap generation
-        let mut tile_map = TileMap::empty(board_options.map_size.0, board_options.map_size.1);
-        tile_map.set_bombs(board_options.bomb_count);
-        let tile_map = tile_map;
+        // Tilemap generation. ChaCha is portable, seeded boards are the same on every platform
+        let mut rng = match board_options.seed {
+            Some(seed) => ChaCha8Rng::seed_from_u64(seed),
+            None => ChaCha8Rng::from_entropy(),
+        };
+        let tile_map = match unbounded {
+            true => TileMap::empty(0, 0),
+            false => TileMap::generate(&board_options, &mut rng),
+        };
         #[cfg(feature = "debug")]
         // Tilemap debugging
         log::info!("{}", tile_map.console_output());
 
+        // Atlas numbers are capped, larger counts need the sprites and their texts
+        let max_count = tile_map.max_bomb_neighbor_count();
+        if board_options.rendering != TileRendering::Sprites && max_count > TileAtlas::MAX_NUMBER {
+            log::warn!(
+                "Bomb neighbor counts up to {max_count} can't be drawn in a texture atlas, \
+                falling back to sprite rendering"
+            );
+            board_options.rendering = TileRendering::Sprites;
+        }
+
         let window = windows.get_primary().expect("Failed to get primary window");
 
         // We define the size of our tiles in world space
@@ -101,8 +271,71 @@ impl<T> BoardPlugin<T> {
             BoardPosition::Custom(p) => p,
         };
 
-        let loaded_assets = LoadedAssets { bomb_image, font };
+        // The view is centered on the saved player position
+        if let Some(world) = &endless_world {
+            let position = Vec2::new(world.position.x as f32, world.position.y as f32);
+            let position = board_position.xy() + (position + 0.5) * tile_size;
+            for mut transform in cameras.iter_mut() {
+                transform.translation.x = position.x;
+                transform.translation.y = position.y;
+            }
+        }
+
         commands.insert_resource(loaded_assets.clone());
+        commands.init_resource::<TilePress>();
+
+        // Boards rendered in chunks are played by the rule engine instead of tile entities
+        match (&endless_world, board_options.rendering) {
+            (Some(world), _) => {
+                let stats = GameStats {
+                    tiles_uncovered: world.score(),
+                    ..GameStats::new(1, 0)
+                };
+                commands.insert_resource(stats);
+            }
+            (None, TileRendering::Chunked) => {
+                let mut game = Game::new(tile_map.clone(), board_options.lives);
+                if board_options.safe_start {
+                    game.uncover_safe_start();
+                }
+                commands.insert_resource(game.stats().clone());
+                commands.insert_resource(ChunkMap::new(game, board_options.tile_padding));
+            }
+            (None, TileRendering::Sprites | TileRendering::Atlas) => {
+                let stats = GameStats::new(board_options.lives.max(1), tile_map.bbbv());
+                commands.insert_resource(stats);
+            }
+        }
+
+        let max_number = match &endless_world {
+            Some(world) => world.max_number(),
+            None => tile_map.max_bomb_neighbor_count(),
+        };
+        let atlas = match (board_options.rendering, unbounded) {
+            (TileRendering::Sprites, false) => None,
+            _ => Some(TileAtlas::new(
+                &theme,
+                max_number,
+                &mut images,
+                &mut atlases,
+            )),
+        };
+        if let Some(atlas) = &atlas {
+            commands.insert_resource(atlas.clone());
+        }
+
+        if let Some(world) = endless_world {
+            commands.insert_resource(world);
+        }
+
+        // Lives are only displayed when the player can survive a mine, the endless world score
+        // is always displayed
+        if board_options.lives > 1 || unbounded {
+            Self::spawn_hud(&mut commands, &loaded_assets);
+        }
+        if let Some(daily) = daily {
+            Self::spawn_daily_label(&mut commands, &loaded_assets, &daily);
+        }
 
         Self::spawn_board(
             commands,
@@ -112,22 +345,23 @@ impl<T> BoardPlugin<T> {
             tile_size,
             board_options,
             &loaded_assets,
+            &theme,
+            atlas.as_ref(),
+            unbounded,
         );
     }
 
     /// Generates the bomb counter text 2D Bundle for a given value
-    fn bomb_count_text_bundle(count: u8, font: Handle<Font>, size: f32) -> Text2dBundle {
+    fn bomb_count_text_bundle(
+        count: u8,
+        font: Handle<Font>,
+        theme: &BoardTheme,
+        size: f32,
+    ) -> Text2dBundle {
         // We retrieve the text and the correct color
-        let (text, color) = (
-            count.to_string(),
-            match count {
-                1 => Color::WHITE,
-                2 => Color::GREEN,
-                3 => Color::YELLOW,
-                4 => Color::ORANGE,
-                _ => Color::PURPLE,
-            },
-        );
+        let (text, color) = (count.to_string(), theme.number_color(count));
+        // Multiple digits must still fit in the tile
+        let size = size / text.len() as f32;
         // We generate a text bundle
         Text2dBundle {
             text: Text {
@@ -158,9 +392,11 @@ impl<T> BoardPlugin<T> {
         tile_size: f32,
         board_options: BoardOptions,
         loaded_assets: &LoadedAssets,
+        theme: &BoardTheme,
+        atlas: Option<&TileAtlas>,
+        unbounded: bool,
     ) {
-        let mut covered_tiles =
-            HashMap::with_capacity((tile_map.width() * tile_map.height()) as usize);
+        let mut covered_tiles = HashMap::with_capacity(tile_map.tile_count());
 
         let mut safe_start = None;
 
@@ -175,30 +411,66 @@ impl<T> BoardPlugin<T> {
                 ..Default::default()
             })
             .with_children(|parent| {
-                // We spawn the board background sprite at the center of the board, since the sprite pivot is centered
+                // We spawn the board background sprite at the center of the board, since the sprite pivot is centered.
+                // Shaped boards have no background, to keep their outline visible, and endless
+                // boards have no size
+                if board_options.mask.is_none() && !unbounded {
+                    parent
+                        .spawn(SpriteBundle {
+                            sprite: Sprite {
+                                color: theme.background_color,
+                                custom_size: Some(board_size),
+                                anchor: Anchor::BottomLeft,
+                                ..Default::default()
+                            },
+                            ..Default::default()
+                        })
+                        .insert(Name::new("Background"))
+                        .insert(BoardBackground);
+                }
+
+                // Keyboard and gamepad cursor, above the tiles and their overlays
                 parent
                     .spawn(SpriteBundle {
                         sprite: Sprite {
-                            color: Color::WHITE,
-                            custom_size: Some(board_size),
-                            anchor: Anchor::BottomLeft,
+                            color: Color::rgba(1., 1., 0., 0.4),
+                            custom_size: Some(Vec2::splat(tile_size)),
                             ..Default::default()
                         },
+                        transform: Transform::from_translation(
+                            TileCursor::default().translation(tile_size),
+                        ),
+                        visibility: Visibility::INVISIBLE,
                         ..Default::default()
                     })
-                    .insert(Name::new("Background"));
-
-                Self::spawn_tiles(
-                    parent,
-                    &tile_map,
-                    tile_size,
-                    board_options.tile_padding,
-                    Color::GRAY,
-                    loaded_assets,
-                    Color::DARK_GRAY,
-                    &mut covered_tiles,
-                    &mut safe_start,
-                );
+                    .insert(Name::new("Tile Cursor"))
+                    .insert(TileCursor::default());
+
+                match (board_options.rendering, atlas) {
+                    // Chunks are spawned around the camera by `system::chunk::update_chunks`
+                    (TileRendering::Chunked, _) => (),
+                    (_, Some(atlas)) => Self::spawn_atlas_tiles(
+                        parent,
+                        &tile_map,
+                        tile_size,
+                        board_options.tile_padding,
+                        atlas,
+                        loaded_assets,
+                        theme,
+                        &mut covered_tiles,
+                        &mut safe_start,
+                    ),
+                    (_, None) => Self::spawn_tiles(
+                        parent,
+                        &tile_map,
+                        tile_size,
+                        board_options.tile_padding,
+                        loaded_assets,
+                        theme,
+                        &mut covered_tiles,
+                        &mut safe_start,
+                    ),
+                }
             })
             .id();
 
@@ -219,6 +491,9 @@ impl<T> BoardPlugin<T> {
             },
             tile_size,
             covered_tiles,
+            marked_tiles: HashMap::new(),
+            exploded_tiles: HashSet::new(),
+            unbounded,
             entity: Some(board_entity),
         });
     }
@@ -229,23 +504,26 @@ impl<T> BoardPlugin<T> {
         tile_map: &TileMap,
         size: f32,
         padding: f32,
-        color: Color,
         loaded_assets: &LoadedAssets,
-        covered_tile_color: Color,
+        theme: &BoardTheme,
         covered_tiles: &mut HashMap<Coordinates, Entity>,
         safe_start_entity: &mut Option<Entity>,
     ) {
         // Tiles
         for (y, line) in tile_map.iter().enumerate() {
             for (x, tile) in line.iter().enumerate() {
+                // Disabled tiles are out of the board shape
+                if tile.is_disabled() {
+                    continue;
+                }
                 let coordinates = Coordinates {
-                    x: x as u16,
-                    y: y as u16,
+                    x: x as i32,
+                    y: y as i32,
                 };
                 let mut cmd = parent.spawn_empty();
                 cmd.insert(SpriteBundle {
                     sprite: Sprite {
-                        color,
+                        color: theme.revealed_color,
                         custom_size: Some(Vec2::splat(size - padding)),
                         ..Default::default()
                     },
@@ -264,7 +542,7 @@ impl<T> BoardPlugin<T> {
                         .spawn(SpriteBundle {
                             sprite: Sprite {
                                 custom_size: Some(Vec2::splat(size - padding)),
-                                color: covered_tile_color,
+                                color: theme.cover_color,
                                 ..Default::default()
                             },
                             transform: Transform::from_xyz(0., 0., 3.),
@@ -280,17 +558,29 @@ impl<T> BoardPlugin<T> {
                 });
 
                 match tile {
-                    Tile::Bomb => {
-                        cmd.insert(Bomb).with_children(|parent| {
-                            parent.spawn(SpriteBundle {
-                                sprite: Sprite {
-                                    custom_size: Some(Vec2::splat(size - padding)),
+                    Tile::Bomb(n) => {
+                        cmd.insert(Bomb { count: *n }).with_children(|parent| {
+                            parent
+                                .spawn(SpriteBundle {
+                                    sprite: Sprite {
+                                        custom_size: Some(Vec2::splat(size - padding)),
+                                        ..Default::default()
+                                    },
+                                    transform: Transform::from_xyz(0., 0., 2.),
+                                    texture: loaded_assets.bomb_image.clone(),
                                     ..Default::default()
-                                },
-                                transform: Transform::from_xyz(0., 0., 2.),
-                                texture: loaded_assets.bomb_image.clone(),
-                                ..Default::default()
-                            });
+                                })
+                                .with_children(|parent| {
+                                    // Stacked mines display their count
+                                    if *n > 1 {
+                                        parent.spawn(stack_count_text_bundle(
+                                            *n,
+                                            loaded_assets.font.clone(),
+                                            theme.stack_count_color,
+                                            size - padding,
+                                        ));
+                                    }
+                                });
                         });
                     }
                     Tile::BombNeighbor(n) => {
@@ -299,16 +589,154 @@ impl<T> BoardPlugin<T> {
                                 parent.spawn(Self::bomb_count_text_bundle(
                                     *n,
                                     loaded_assets.font.clone(),
+                                    theme,
                                     size - padding,
                                 ));
                             });
                     }
-                    Tile::Empty => (),
+                    Tile::Empty | Tile::Disabled => (),
                 };
             }
         }
     }
 
+    /// Spawns the tiles as texture atlas sprites: a single sprite per tile showing its uncovered
+    /// state, and its cover. Only stacked mines still need a text
+    #[allow(clippy::too_many_arguments)]
+    fn spawn_atlas_tiles(
+        parent: &mut ChildBuilder,
+        tile_map: &TileMap,
+        size: f32,
+        padding: f32,
+        atlas: &TileAtlas,
+        loaded_assets: &LoadedAssets,
+        theme: &BoardTheme,
+        covered_tiles: &mut HashMap<Coordinates, Entity>,
+        safe_start_entity: &mut Option<Entity>,
+    ) {
+        let sprite = |index| TextureAtlasSprite {
+            index,
+            custom_size: Some(Vec2::splat(size - padding)),
+            ..Default::default()
+        };
+        for (y, line) in tile_map.iter().enumerate() {
+            for (x, tile) in line.iter().enumerate() {
+                // Disabled tiles are out of the board shape
+                if tile.is_disabled() {
+                    continue;
+                }
+                let coordinates = Coordinates {
+                    x: x as i32,
+                    y: y as i32,
+                };
+                let mut cmd = parent.spawn(SpriteSheetBundle {
+                    sprite: sprite(atlas.tile_index(tile)),
+                    texture_atlas: atlas.texture_atlas.clone(),
+                    transform: Transform::from_xyz(
+                        (x as f32 * size) + (size / 2.),
+                        (y as f32 * size) + (size / 2.),
+                        1.,
+                    ),
+                    ..Default::default()
+                });
+                cmd.insert(Name::new(format!("Tile ({}, {})", x, y)))
+                    .insert(coordinates)
+                    .with_children(|parent| {
+                        let entity = parent
+                            .spawn(SpriteSheetBundle {
+                                sprite: sprite(TileAtlas::COVER),
+                                texture_atlas: atlas.texture_atlas.clone(),
+                                transform: Transform::from_xyz(0., 0., 3.),
+                                ..Default::default()
+                            })
+                            .insert(Name::new("Tile Cover"))
+                            .id();
+                        covered_tiles.insert(coordinates, entity);
+
+                        if safe_start_entity.is_none() && *tile == Tile::Empty {
+                            *safe_start_entity = Some(entity);
+                        }
+                    });
+
+                match tile {
+                    Tile::Bomb(n) => {
+                        cmd.insert(Bomb { count: *n }).with_children(|parent| {
+                            // Stacked mines display their count
+                            if *n > 1 {
+                                parent.spawn(stack_count_text_bundle(
+                                    *n,
+                                    loaded_assets.font.clone(),
+                                    theme.stack_count_color,
+                                    size - padding,
+                                ));
+                            }
+                        });
+                    }
+                    Tile::BombNeighbor(n) => {
+                        cmd.insert(BombNeighbor { count: *n });
+                    }
+                    Tile::Empty | Tile::Disabled => (),
+                };
+            }
+        }
+    }
+
+    /// Spawns the heads-up display text, filled by `system::hud::update_hud`
+    fn spawn_hud(commands: &mut Commands, loaded_assets: &LoadedAssets) {
+        commands
+            .spawn(
+                TextBundle::from_section(
+                    "",
+                    TextStyle {
+                        font: loaded_assets.font.clone(),
+                        font_size: 20.,
+                        color: Color::WHITE,
+                    },
+                )
+                .with_style(Style {
+                    position_type: PositionType::Absolute,
+                    position: UiRect {
+                        top: Val::Px(10.),
+                        left: Val::Px(10.),
+                        ..Default::default()
+                    },
+                    ..Default::default()
+                }),
+            )
+            .insert(Name::new("HUD"))
+            .insert(Hud);
+    }
+
+    fn spawn_daily_label(
+        commands: &mut Commands,
+        loaded_assets: &LoadedAssets,
+        daily: &DailyChallenge,
+    ) {
+        let official = if daily.official { "" } else { " (unofficial)" };
+        commands
+            .spawn(
+                TextBundle::from_section(
+                    format!("Daily {}{official}", daily.date),
+                    TextStyle {
+                        font: loaded_assets.font.clone(),
+                        font_size: 20.,
+                        color: Color::WHITE,
+                    },
+                )
+                .with_style(Style {
+                    position_type: PositionType::Absolute,
+                    position: UiRect {
+                        top: Val::Px(10.),
+                        right: Val::Px(10.),
+                        ..Default::default()
+                    },
+                    ..Default::default()
+                }),
+            )
+            .insert(Name::new("Daily label"))
+            .insert(DailyLabel);
+    }
+
     /// Computes a tile size that matches the window according to the tile map size
     fn adaptative_tile_size(
         window: &Window,
@@ -320,8 +748,109 @@ impl<T> BoardPlugin<T> {
         max_width.min(max_height).clamp(min, max)
     }
 
-    fn cleanup_board(mut commands: Commands, board: Res<Board>) {
+    /// Hides the board of an ongoing game while the running state is paused, the game timer
+    /// and inputs are stopped since they only run in the running state
+    fn hide_board(
+        board: Res<Board>,
+        stats: Res<GameStats>,
+        mut visibilities: Query<&mut Visibility>,
+    ) {
+        if stats.is_finished() {
+            return;
+        }
+        if let Some(mut visibility) = board.entity.and_then(|e| visibilities.get_mut(e).ok()) {
+            visibility.is_visible = false;
+        }
+    }
+
+    /// Displays the board again when the running state is resumed
+    fn show_board(board: Res<Board>, mut visibilities: Query<&mut Visibility>) {
+        if let Some(mut visibility) = board.entity.and_then(|e| visibilities.get_mut(e).ok()) {
+            visibility.is_visible = true;
+        }
+    }
+
+    fn cleanup_board(
+        mut commands: Commands,
+        board: Res<Board>,
+        hud: Query<Entity, With<Hud>>,
+        leaderboards: Query<Entity, With<Leaderboard>>,
+        stats_screens: Query<Entity, With<StatsScreen>>,
+        daily_labels: Query<Entity, With<DailyLabel>>,
+        endless_world: Option<Res<EndlessWorld>>,
+    ) {
         commands.entity(board.entity.unwrap()).despawn_recursive();
+        let overlays = hud.iter().chain(&leaderboards).chain(&stats_screens);
+        for entity in overlays.chain(&daily_labels) {
+            commands.entity(entity).despawn_recursive();
+        }
         commands.remove_resource::<Board>();
+        commands.remove_resource::<GameStats>();
+        commands.remove_resource::<TilePress>();
+        commands.remove_resource::<TileAtlas>();
+        commands.remove_resource::<ChunkMap>();
+        // The endless world is resumed on the next endless game
+        if let Some(world) = endless_world {
+            if let Err(e) = world.save() {
+                log::error!("Failed to save the endless world: {e}");
+            }
+            commands.remove_resource::<EndlessWorld>();
+        }
     }
 }
+
+/// Piped after a `BoardRunCriteria`, runs on boards with tile entities only
+fn tile_entities(
+    In(should_run): In<ShouldRun>,
+    chunk_map: Option<Res<ChunkMap>>,
+    endless_world: Option<Res<EndlessWorld>>,
+) -> ShouldRun {
+    match (chunk_map, endless_world) {
+        (None, None) => should_run,
+        _ => ShouldRun::No,
+    }
+}
+
+/// Piped after a `BoardRunCriteria`, runs on boards rendered in chunks only
+fn chunks(In(should_run): In<ShouldRun>, chunk_map: Option<Res<ChunkMap>>) -> ShouldRun {
+    match chunk_map {
+        Some(_) => should_run,
+        None => ShouldRun::No,
+    }
+}
+
+/// Piped after a `BoardRunCriteria`, runs in the endless world only
+fn endless(In(should_run): In<ShouldRun>, endless_world: Option<Res<EndlessWorld>>) -> ShouldRun {
+    match endless_world {
+        Some(_) => should_run,
+        None => ShouldRun::No,
+    }
+}
+
+/// Generates the stacked mines or flags count text 2D Bundle, displayed in the bottom right corner
+pub(crate) fn stack_count_text_bundle(
+    count: u8,
+    font: Handle<Font>,
+    color: Color,
+    size: f32,
+) -> (Text2dBundle, StackCount) {
+    let bundle = Text2dBundle {
+        text: Text {
+            sections: vec![TextSection {
+                value: count.to_string(),
+                style: TextStyle {
+                    color,
+                    font,
+                    font_size: size / 2.,
+                },
+            }],
+            alignment: TextAlignment {
+                vertical: VerticalAlign::Center,
+                horizontal: HorizontalAlign::Center,
+            },
+        },
+        transform: Transform::from_xyz(size / 4., -size / 4., 1.),
+        ..Default::default()
+    };
+    (bundle, StackCount)
+}
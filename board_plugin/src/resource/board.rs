@@ -1,5 +1,11 @@
-use crate::{Coordinates, TileMap};
-use bevy::{prelude::*, utils::HashMap};
+use crate::{
+    solver::{BoardView, TileView},
+    Coordinates, Tile, TileMap,
+};
+use bevy::{
+    prelude::*,
+    utils::{HashMap, HashSet},
+};
 
 #[cfg(feature = "debug")]
 use bevy_inspector_egui::prelude::*;
@@ -15,43 +21,154 @@ pub struct Board {
     pub bounds: Rect,
     pub tile_size: f32,
     pub covered_tiles: HashMap<Coordinates, Entity>,
+    /// Flag count of the marked tiles
+    pub marked_tiles: HashMap<Coordinates, u8>,
+    /// Mined tiles the player stepped on and survived, their flags are locked
+    pub exploded_tiles: HashSet<Coordinates>,
+    /// Endless boards have no bounds, `bounds.min` is the origin of the tile coordinates
+    pub unbounded: bool,
     pub entity: Option<Entity>,
 }
 
 impl Board {
-    /// Translates a mouse position to board coordinates
-    pub fn mouse_position(&self, window: &Window, position: Vec2) -> Option<Coordinates> {
+    /// Translates a window position (bottom left origin) to board coordinates. The position is
+    /// projected through the camera when given, to account for zoom and pan
+    pub fn mouse_position(
+        &self,
+        window: &Window,
+        camera: Option<(&Camera, &GlobalTransform)>,
+        position: Vec2,
+    ) -> Option<Coordinates> {
         // Window to world space
-        let window_size = Vec2::new(window.width(), window.height());
-        let position = position - window_size / 2.;
+        let position = match camera.and_then(|(c, t)| c.viewport_to_world(t, position)) {
+            Some(ray) => ray.origin.truncate(),
+            None => position - Vec2::new(window.width(), window.height()) / 2.,
+        };
 
-        // Bounds check
-        self.bounds.contains(position).then(|| {
+        // Bounds check, the top and right edges belong to the tiles past the board
+        let inside = position.cmpge(self.bounds.min).all() && position.cmplt(self.bounds.max).all();
+        (self.unbounded || inside).then(|| {
             // World space to board space
-            let coordinates = position - self.bounds.min;
+            let coordinates = (position - self.bounds.min) / self.tile_size;
             Coordinates {
-                x: (coordinates.x / self.tile_size) as u16,
-                y: (coordinates.y / self.tile_size) as u16,
+                x: coordinates.x.floor() as i32,
+                y: coordinates.y.floor() as i32,
             }
         })
     }
 
-    /// Retrieves a covered tile entity
+    /// Retrieves a covered tile entity, marked tiles can't be uncovered
     pub fn tile_to_uncover(&self, coords: &Coordinates) -> Option<&Entity> {
+        if self.marked_tiles.contains_key(coords) {
+            return None;
+        }
         self.covered_tiles.get(coords)
     }
 
     /// We try to uncover a tile, returning the entity
     pub fn try_uncover_tile(&mut self, coords: &Coordinates) -> Option<Entity> {
+        self.marked_tiles.remove(coords);
         self.covered_tiles.remove(coords)
     }
 
-    /// We retrieve the adjacent covered tile entities of `coord`
+    /// We retrieve the adjacent covered tile entities of `coord`, ignoring marked tiles
     pub fn adjacent_covered_tiles(&self, coord: Coordinates) -> Vec<Entity> {
         self.tile_map
             .safe_square_at(coord)
-            .filter_map(|c| self.covered_tiles.get(&c))
+            .filter_map(|c| self.tile_to_uncover(&c))
             .copied()
             .collect()
     }
+
+    /// Retrieves the covered neighbor entities to uncover when chording on `coords`.
+    ///
+    /// Chording requires an uncovered bomb neighbor whose neighbor flags match its count
+    pub fn chord_targets(&self, coords: &Coordinates) -> Option<Vec<Entity>> {
+        if self.covered_tiles.contains_key(coords) {
+            return None;
+        }
+        let Tile::BombNeighbor(count) = self
+            .tile_map
+            .get(coords.y as usize)?
+            .get(coords.x as usize)?
+        else {
+            return None;
+        };
+        let flags: u16 = self
+            .tile_map
+            .safe_square_at(*coords)
+            .filter_map(|c| self.marked_tiles.get(&c))
+            .map(|flags| *flags as u16)
+            .sum();
+        (flags == *count as u16).then(|| self.adjacent_covered_tiles(*coords))
+    }
+
+    /// Is every safe tile uncovered
+    pub fn is_completed(&self) -> bool {
+        self.covered_tiles.len() == self.tile_map.mined_tile_count()
+    }
+
+    /// Flags an exploded tile with `flags` flags, returning the tile cover entity
+    pub fn try_mark_exploded(&mut self, coords: &Coordinates, flags: u8) -> Option<Entity> {
+        let entity = *self.covered_tiles.get(coords)?;
+        self.marked_tiles.insert(*coords, flags);
+        self.exploded_tiles.insert(*coords);
+        Some(entity)
+    }
+
+    /// Cycles the flag count of a covered tile through `1..=max_stack` and back to unmarked.
+    ///
+    /// Returns the tile cover entity and its new flag count (`0` when unmarked)
+    pub fn try_toggle_mark(&mut self, coords: &Coordinates) -> Option<(Entity, u8)> {
+        if self.exploded_tiles.contains(coords) {
+            return None;
+        }
+        let entity = *self.covered_tiles.get(coords)?;
+        let max_stack = self.tile_map.max_stack().max(1);
+        let flags = match self.marked_tiles.get(coords) {
+            Some(n) if *n < max_stack => n + 1,
+            Some(_) => 0,
+            None => 1,
+        };
+        if flags == 0 {
+            self.marked_tiles.remove(coords);
+        } else {
+            self.marked_tiles.insert(*coords, flags);
+        }
+        Some((entity, flags))
+    }
+
+    /// Builds the public view of the board, as seen by the player
+    pub fn view(&self) -> BoardView {
+        let mut view = BoardView::new(
+            self.tile_map.width(),
+            self.tile_map.height(),
+            self.tile_map.neighborhood().clone(),
+            self.tile_map.bomb_count(),
+            self.tile_map.max_stack(),
+        );
+        for (y, line) in self.tile_map.iter().enumerate() {
+            for (x, tile) in line.iter().enumerate() {
+                let coords = Coordinates {
+                    x: x as i32,
+                    y: y as i32,
+                };
+                let tile = match tile {
+                    Tile::Disabled => TileView::Disabled,
+                    Tile::Bomb(n) if self.exploded_tiles.contains(&coords) => TileView::Mine(*n),
+                    _ if self.covered_tiles.contains_key(&coords) => {
+                        match self.marked_tiles.get(&coords) {
+                            Some(flags) => TileView::Flagged(*flags),
+                            None => TileView::Covered,
+                        }
+                    }
+                    Tile::Bomb(n) => TileView::Mine(*n),
+                    Tile::BombNeighbor(n) => TileView::Revealed(*n),
+                    Tile::Empty => TileView::Revealed(0),
+                };
+                view.set(coords, tile);
+            }
+        }
+        view
+    }
 }
@@ -0,0 +1,121 @@
+use bevy::render::{render_resource::TextureFormat, texture::Image};
+use serde::{Deserialize, Serialize};
+
+#[cfg(feature = "debug")]
+use bevy::prelude::{FromReflect, Reflect};
+
+use crate::component::Coordinates;
+
+/// Board shape mask, defining which tiles of the board are enabled.
+///
+/// Disabled tiles get no tile entity, are never mined and are ignored by neighbor counts
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[cfg_attr(feature = "debug", derive(Reflect, FromReflect))]
+pub struct BoardMask {
+    width: u16,
+    height: u16,
+    /// Row major enabled flags, starting from the bottom left tile
+    cells: Vec<bool>,
+}
+
+impl BoardMask {
+    /// Builds a mask from a predicate on the tile coordinates
+    pub fn from_fn(width: u16, height: u16, mut enabled: impl FnMut(Coordinates) -> bool) -> Self {
+        let cells = (0..height as i32)
+            .flat_map(|y| (0..width as i32).map(move |x| Coordinates { x, y }))
+            .map(&mut enabled)
+            .collect();
+        Self {
+            width,
+            height,
+            cells,
+        }
+    }
+
+    /// Rectangular mask with every tile enabled
+    pub fn full(width: u16, height: u16) -> Self {
+        Self::from_fn(width, height, |_| true)
+    }
+
+    /// Elliptic mask fitting the `width` x `height` rectangle (a circle for square dimensions)
+    pub fn ellipse(width: u16, height: u16) -> Self {
+        Self::from_fn(width, height, |c| {
+            let (x, y) = Self::normalized(c, width, height);
+            x * x + y * y <= 1.
+        })
+    }
+
+    /// Heart shaped mask fitting the `width` x `height` rectangle
+    pub fn heart(width: u16, height: u16) -> Self {
+        Self::from_fn(width, height, |c| {
+            let (x, y) = Self::normalized(c, width, height);
+            // Heart curve: (x² + y² - 1)³ - x²y³ <= 0, slightly scaled to fill the rectangle
+            let (x, y) = (x * 1.15, y * 1.2 + 0.15);
+            let a = x * x + y * y - 1.;
+            a * a * a - x * x * y * y * y <= 0.
+        })
+    }
+
+    /// Parses a text mask, where the first line is the top row of the board.
+    ///
+    /// `.` and spaces are disabled tiles, any other character is an enabled tile.
+    /// Lines shorter than the longest one are padded with disabled tiles
+    pub fn from_text(text: &str) -> Self {
+        let lines: Vec<Vec<bool>> = text
+            .lines()
+            .filter(|line| !line.trim().is_empty())
+            .map(|line| line.chars().map(|c| !matches!(c, '.' | ' ')).collect())
+            .collect();
+        let width = lines.iter().map(Vec::len).max().unwrap_or_default() as u16;
+        let height = lines.len() as u16;
+        Self::from_fn(width, height, |Coordinates { x, y }| {
+            let line = &lines[(height as i32 - 1 - y) as usize];
+            line.get(x as usize).copied().unwrap_or_default()
+        })
+    }
+
+    /// Builds a mask from an image, with one pixel per tile.
+    ///
+    /// Opaque pixels are enabled tiles, transparent pixels are disabled tiles.
+    /// Returns `None` if the image format can't be converted
+    pub fn from_image(image: &Image) -> Option<Self> {
+        let image = image.convert(TextureFormat::Rgba8UnormSrgb)?;
+        let size = image.texture_descriptor.size;
+        let (width, height) = (size.width as u16, size.height as u16);
+        Some(Self::from_fn(width, height, |Coordinates { x, y }| {
+            // Image rows start from the top
+            let index = ((height as i32 - 1 - y) as usize * width as usize + x as usize) * 4;
+            image.data.get(index + 3).is_some_and(|alpha| *alpha >= 128)
+        }))
+    }
+
+    /// Getter for `width`
+    pub fn width(&self) -> u16 {
+        self.width
+    }
+
+    /// Getter for `height`
+    pub fn height(&self) -> u16 {
+        self.height
+    }
+
+    /// Is the tile at `coordinates` enabled. Out of bounds tiles are disabled
+    pub fn is_enabled(&self, Coordinates { x, y }: Coordinates) -> bool {
+        if !(Coordinates { x, y }).is_within((self.width, self.height)) {
+            return false;
+        }
+        self.cells[y as usize * self.width as usize + x as usize]
+    }
+
+    /// Number of enabled tiles
+    pub fn enabled_count(&self) -> usize {
+        self.cells.iter().filter(|enabled| **enabled).count()
+    }
+
+    /// Maps tile coordinates to the `[-1, 1]` range, centered on the mask
+    fn normalized(Coordinates { x, y }: Coordinates, width: u16, height: u16) -> (f32, f32) {
+        let x = (x as f32 + 0.5) / width as f32 * 2. - 1.;
+        let y = (y as f32 + 0.5) / height as f32 * 2. - 1.;
+        (x, y)
+    }
+}
@@ -1,6 +1,9 @@
 use bevy::prelude::{Resource, Vec3};
+use derive_more::Display;
 use serde::{Deserialize, Serialize};
 
+use super::{BoardMask, MineDistribution, Neighborhood};
+
 #[cfg(feature = "debug")]
 use bevy::prelude::{Reflect, ReflectResource};
 
@@ -24,23 +27,69 @@ pub enum BoardPosition {
     Custom(Vec3),
 }
 
+/// Tile rendering options
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[cfg_attr(feature = "debug", derive(Reflect))]
+pub enum TileRendering {
+    /// Separate sprites and texts per tile
+    #[default]
+    Sprites,
+    /// Single sprite per tile from a generated texture atlas, for large boards
+    Atlas,
+    /// Texture atlas sprites spawned for the chunks in view only, the game state is kept out of
+    /// the entities. For massive boards, hints, the heatmap and stacked counts are not displayed
+    Chunked,
+}
+
 /// Board generation options. Must be used as a resource
 // We use serde to allow saving option presets and loading them at runtime
 #[derive(Debug, Clone, Serialize, Deserialize, Resource)]
 #[cfg_attr(feature = "debug", derive(Reflect), reflect(Resource))]
 pub struct BoardOptions {
-    /// Tile map size
+    /// Tile map size, ignored if a `mask` is set
     pub map_size: (u16, u16),
+    /// Optional board shape, overriding `map_size` with its own dimensions
+    pub mask: Option<BoardMask>,
     /// bomb count
     pub bomb_count: u16,
+    /// Distribution of the number of mines stacked on a mined tile
+    pub mine_distribution: MineDistribution,
     /// Board world position
     pub position: BoardPosition,
     /// Tile world size
     pub tile_size: TileSize,
     /// Padding between tiles
     pub tile_padding: f32,
+    /// Tile rendering method
+    pub rendering: TileRendering,
     /// Does the board generate a safe place to start
     pub safe_start: bool,
+    /// Number of mines the player can step on before losing, hit mines get flagged
+    pub lives: u8,
+    /// Neighborhood rule used for bomb counts and uncovering propagation
+    pub neighborhood: Neighborhood,
+    /// Board generation seed, every board is different if unset
+    pub seed: Option<u64>,
+}
+
+/// Invalid board options
+#[derive(Debug, Clone, Copy, PartialEq, Display)]
+pub enum BoardOptionsError {
+    /// The map width is out of bounds
+    #[display(fmt = "Width must be between {} and {}", min, max)]
+    InvalidWidth { min: u16, max: u16 },
+    /// The map height is out of bounds
+    #[display(fmt = "Height must be between {} and {}", min, max)]
+    InvalidHeight { min: u16, max: u16 },
+    /// The board has no mine
+    #[display(fmt = "The board needs at least one mine")]
+    NoMines,
+    /// The mine density is too high for the board to be playable
+    #[display(fmt = "At most {} mines fit on this board", max)]
+    TooManyMines { max: u16 },
+    /// The padding swallows the tiles
+    #[display(fmt = "Padding must be lower than {} px", max)]
+    InvalidPadding { max: f32 },
 }
 
 impl Default for TileSize {
@@ -61,11 +110,105 @@ impl Default for BoardOptions {
     fn default() -> Self {
         Self {
             map_size: (15, 15),
+            mask: None,
             bomb_count: 30,
+            mine_distribution: Default::default(),
             position: Default::default(),
             tile_size: Default::default(),
             tile_padding: 0.,
+            rendering: Default::default(),
             safe_start: true,
+            lives: 1,
+            neighborhood: Default::default(),
+            seed: None,
         }
     }
 }
+
+impl BoardOptions {
+    /// Minimum map width and height
+    pub const MIN_MAP_SIZE: u16 = 3;
+    /// Maximum map width and height
+    pub const MAX_MAP_SIZE: u16 = 100;
+
+    /// Tile map dimensions, from the `mask` if any
+    pub fn dimensions(&self) -> (u16, u16) {
+        self.mask
+            .as_ref()
+            .map_or(self.map_size, |m| (m.width(), m.height()))
+    }
+
+    /// Maximum number of mines, keeping the safe start area (or at least one tile) free
+    pub fn max_bomb_count(&self) -> u16 {
+        let (width, height) = self.dimensions();
+        let tiles = match &self.mask {
+            Some(mask) => mask.enabled_count(),
+            None => width as usize * height as usize,
+        };
+        let reserved = match self.safe_start {
+            true => self.neighborhood.len() + 1,
+            false => 1,
+        };
+        let max = tiles.saturating_sub(reserved) * self.mine_distribution.max_stack() as usize;
+        max.try_into().unwrap_or(u16::MAX)
+    }
+
+    /// Checks the options, returning every issue found
+    pub fn validate(&self) -> Result<(), Vec<BoardOptionsError>> {
+        let (min, max) = (Self::MIN_MAP_SIZE, Self::MAX_MAP_SIZE);
+        let (width, height) = self.dimensions();
+        let mut errors = Vec::new();
+        if !(min..=max).contains(&width) {
+            errors.push(BoardOptionsError::InvalidWidth { min, max });
+        }
+        if !(min..=max).contains(&height) {
+            errors.push(BoardOptionsError::InvalidHeight { min, max });
+        }
+        let max_bomb_count = self.max_bomb_count();
+        if self.bomb_count == 0 {
+            errors.push(BoardOptionsError::NoMines);
+        } else if self.bomb_count > max_bomb_count {
+            errors.push(BoardOptionsError::TooManyMines {
+                max: max_bomb_count,
+            });
+        }
+        let smallest_tile = match self.tile_size {
+            TileSize::Fixed(size) => size,
+            TileSize::Adaptive { min, .. } => min,
+        };
+        if self.tile_padding < 0. || self.tile_padding >= smallest_tile {
+            errors.push(BoardOptionsError::InvalidPadding { max: smallest_tile });
+        }
+        match errors.is_empty() {
+            true => Ok(()),
+            false => Err(errors),
+        }
+    }
+
+    /// Stable identifier of the difficulty, hashing the options affecting the gameplay only
+    pub fn difficulty_key(&self) -> String {
+        let gameplay = (
+            self.dimensions(),
+            &self.mask,
+            self.bomb_count,
+            &self.mine_distribution,
+            self.safe_start,
+            self.lives,
+            &self.neighborhood,
+        );
+        let serialized = serde_json::to_vec(&gameplay).expect("Failed to serialize board options");
+        // FNV-1a, stable across builds unlike the std hasher
+        let hash = serialized
+            .iter()
+            .fold(0xcbf2_9ce4_8422_2325_u64, |hash, byte| {
+                (hash ^ *byte as u64).wrapping_mul(0x0100_0000_01b3)
+            });
+        format!("{hash:016x}")
+    }
+
+    /// Human readable difficulty description
+    pub fn difficulty_label(&self) -> String {
+        let (width, height) = self.dimensions();
+        format!("{width}x{height}, {} mines", self.bomb_count)
+    }
+}
@@ -0,0 +1,132 @@
+use bevy::prelude::{Color, Resource};
+
+/// Visual theme of the board, switchable at runtime
+#[derive(Debug, Clone, PartialEq, Resource)]
+pub struct BoardTheme {
+    /// Theme display name, identifying the built-in themes
+    pub name: String,
+    /// Board background color, drawn between the tiles
+    pub background_color: Color,
+    /// Uncovered tile color
+    pub revealed_color: Color,
+    /// Tile cover color
+    pub cover_color: Color,
+    /// Detonated mine tile color
+    pub detonated_color: Color,
+    /// Bomb neighbor count colors, starting with `1`
+    pub number_colors: Vec<Color>,
+    /// Stacked mines and flags count color
+    pub stack_count_color: Color,
+    /// Font asset path
+    pub font: String,
+    /// Mine sprite asset path
+    pub bomb_image: String,
+    /// Flag sprite asset path
+    pub flag_image: String,
+}
+
+impl Default for BoardTheme {
+    fn default() -> Self {
+        Self::classic()
+    }
+}
+
+impl BoardTheme {
+    /// The original look of the board
+    pub fn classic() -> Self {
+        Self {
+            name: "Classic".to_string(),
+            background_color: Color::WHITE,
+            revealed_color: Color::GRAY,
+            cover_color: Color::DARK_GRAY,
+            detonated_color: Color::RED,
+            number_colors: vec![
+                Color::WHITE,
+                Color::GREEN,
+                Color::YELLOW,
+                Color::ORANGE,
+                Color::PURPLE,
+                Color::CYAN,
+                Color::PINK,
+                Color::RED,
+            ],
+            stack_count_color: Color::RED,
+            font: "fonts/pixeled.ttf".to_string(),
+            bomb_image: "sprites/bomb.png".to_string(),
+            flag_image: "sprites/flag.png".to_string(),
+        }
+    }
+
+    /// Low luminance theme
+    pub fn dark() -> Self {
+        Self {
+            name: "Dark".to_string(),
+            background_color: Color::rgb(0.05, 0.05, 0.07),
+            revealed_color: Color::rgb(0.16, 0.16, 0.2),
+            cover_color: Color::rgb(0.3, 0.3, 0.36),
+            detonated_color: Color::rgb(0.6, 0.1, 0.1),
+            number_colors: vec![
+                Color::rgb(0.55, 0.7, 1.),
+                Color::rgb(0.5, 0.85, 0.5),
+                Color::rgb(1., 0.55, 0.55),
+                Color::rgb(0.75, 0.6, 1.),
+                Color::rgb(1., 0.75, 0.4),
+                Color::rgb(0.4, 0.9, 0.9),
+                Color::rgb(0.9, 0.9, 0.9),
+                Color::rgb(0.6, 0.6, 0.6),
+            ],
+            stack_count_color: Color::rgb(1., 0.4, 0.4),
+            ..Self::classic()
+        }
+    }
+
+    /// Maximal contrast between covers, uncovered tiles and numbers
+    pub fn high_contrast() -> Self {
+        Self {
+            name: "High-Contrast".to_string(),
+            background_color: Color::BLACK,
+            revealed_color: Color::WHITE,
+            cover_color: Color::rgb(0.2, 0.2, 0.2),
+            detonated_color: Color::RED,
+            number_colors: vec![
+                Color::BLUE,
+                Color::rgb(0., 0.5, 0.),
+                Color::RED,
+                Color::NAVY,
+                Color::MAROON,
+                Color::TEAL,
+                Color::BLACK,
+                Color::rgb(0.4, 0.4, 0.4),
+            ],
+            stack_count_color: Color::FUCHSIA,
+            ..Self::classic()
+        }
+    }
+
+    /// Built-in themes, in switching order
+    pub fn built_in() -> [Self; 3] {
+        [Self::classic(), Self::dark(), Self::high_contrast()]
+    }
+
+    /// Next built-in theme, custom themes switch back to the first one
+    pub fn next_built_in(&self) -> Self {
+        let themes = Self::built_in();
+        let next = themes
+            .iter()
+            .position(|theme| theme.name == self.name)
+            .map_or(0, |i| (i + 1) % themes.len());
+        themes[next].clone()
+    }
+
+    /// Color of a bomb neighbor count. Custom neighborhoods can go way beyond the color table,
+    /// so we cycle through hues
+    pub fn number_color(&self, count: u8) -> Color {
+        match count
+            .checked_sub(1)
+            .and_then(|i| self.number_colors.get(i as usize))
+        {
+            Some(color) => *color,
+            None => Color::hsl((count as f32 * 37.) % 360., 1., 0.6),
+        }
+    }
+}
@@ -0,0 +1,30 @@
+use std::time::Duration;
+
+use bevy::{
+    prelude::Resource,
+    time::{Timer, TimerMode},
+};
+
+use crate::bot::Bot;
+
+/// Bot playing the board, must be inserted as a resource to let the bot play
+#[derive(Resource)]
+pub struct BotPlayer {
+    /// Bot strategy
+    pub bot: Box<dyn Bot>,
+    /// Timer between two bot actions
+    pub timer: Timer,
+}
+
+impl BotPlayer {
+    /// Creates a bot player, playing `actions_per_second` actions each second
+    pub fn new(bot: impl Bot + 'static, actions_per_second: f32) -> Self {
+        Self {
+            bot: Box::new(bot),
+            timer: Timer::new(
+                Duration::from_secs_f32(1. / actions_per_second.max(f32::EPSILON)),
+                TimerMode::Repeating,
+            ),
+        }
+    }
+}
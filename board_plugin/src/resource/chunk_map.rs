@@ -0,0 +1,77 @@
+use bevy::{prelude::*, utils::HashMap};
+
+use crate::{
+    component::{Chunk, Coordinates},
+    engine::Game,
+};
+
+use super::{GameOutcome, TileAtlas};
+
+/// Game state of a board rendered in chunks.
+///
+/// The tiles are not entities: the state lives in the rule engine, and only the chunks
+/// around the camera are spawned, as texture atlas sprites
+#[derive(Debug, Resource)]
+pub struct ChunkMap {
+    pub game: Game,
+    /// Padding between tiles
+    pub tile_padding: f32,
+    /// Spawned chunk entities
+    pub chunks: HashMap<Chunk, Entity>,
+    /// Tile of the mine that ended the game, if lost
+    pub detonated: Option<Coordinates>,
+    /// Do the spawned chunks need to be redrawn
+    pub dirty: bool,
+}
+
+impl ChunkMap {
+    /// Chunk width and height, in tiles
+    pub const CHUNK_SIZE: i32 = 32;
+
+    pub fn new(game: Game, tile_padding: f32) -> Self {
+        Self {
+            game,
+            tile_padding,
+            chunks: HashMap::new(),
+            detonated: None,
+            dirty: false,
+        }
+    }
+
+    /// Chunk containing `coords`
+    pub fn chunk_at(coords: Coordinates) -> Chunk {
+        Chunk {
+            x: coords.x.div_euclid(Self::CHUNK_SIZE),
+            y: coords.y.div_euclid(Self::CHUNK_SIZE),
+        }
+    }
+
+    /// Coordinates of the enabled tiles of `chunk`
+    pub fn chunk_tiles(&self, chunk: Chunk) -> impl Iterator<Item = Coordinates> + '_ {
+        let tile_map = self.game.tile_map();
+        let (x0, y0) = (chunk.x * Self::CHUNK_SIZE, chunk.y * Self::CHUNK_SIZE);
+        let x1 = (x0 + Self::CHUNK_SIZE).min(tile_map.width() as i32);
+        let y1 = (y0 + Self::CHUNK_SIZE).min(tile_map.height() as i32);
+        (y0..y1)
+            .flat_map(move |y| (x0..x1).map(move |x| Coordinates { x, y }))
+            .filter(|c| !tile_map[c.y as usize][c.x as usize].is_disabled())
+    }
+
+    /// Atlas index displaying the tile at `coords`. Every mine is revealed once the game is lost
+    pub fn tile_index(&self, coords: Coordinates, atlas: &TileAtlas) -> usize {
+        let tile = &self.game.tile_map()[coords.y as usize][coords.x as usize];
+        let lost = self.game.stats().outcome == Some(GameOutcome::Defeat);
+        if self.detonated == Some(coords) {
+            TileAtlas::DETONATED
+        } else if lost && tile.is_bomb() {
+            TileAtlas::MINE
+        } else if self.game.is_covered(coords) {
+            match self.game.flags(coords) {
+                0 => TileAtlas::COVER,
+                _ => TileAtlas::FLAG,
+            }
+        } else {
+            atlas.tile_index(tile)
+        }
+    }
+}
@@ -0,0 +1,148 @@
+use std::{collections::BTreeMap, io, path::PathBuf, time::Duration};
+
+use bevy::{log, prelude::Resource};
+use chrono::{Datelike, Local, NaiveDate};
+use serde::{Deserialize, Serialize};
+
+use super::{storage, BoardOptions, GameOutcome, GameStats};
+
+/// Daily challenge, the board is generated from the calendar date so every player gets the
+/// same one. Must be inserted as a resource to play the daily board
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Resource)]
+pub struct DailyChallenge {
+    /// Date of the challenge
+    pub date: NaiveDate,
+    /// Is the ongoing game the first attempt of the day
+    pub official: bool,
+}
+
+/// Result of the first attempt of a daily challenge
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct DailyResult {
+    /// Game outcome
+    pub outcome: GameOutcome,
+    /// Play time
+    pub time: Duration,
+    /// 3BV solved per second
+    pub bbbv_per_second: f32,
+    /// Number of unofficial replays
+    pub replays: u32,
+}
+
+/// Persistent daily challenge results, keyed by date
+#[derive(Debug, Clone, Default, Resource, Serialize, Deserialize)]
+pub struct DailyResults {
+    /// Storage file, nothing is persisted if unset
+    #[serde(skip)]
+    path: Option<PathBuf>,
+    results: BTreeMap<NaiveDate, DailyResult>,
+}
+
+impl DailyChallenge {
+    /// Challenge of the given date
+    pub fn new(date: NaiveDate) -> Self {
+        Self {
+            date,
+            official: true,
+        }
+    }
+
+    /// Challenge of the local calendar date
+    pub fn today() -> Self {
+        Self::new(Local::now().date_naive())
+    }
+
+    /// Board generation seed of the challenge
+    pub fn seed(&self) -> u64 {
+        self.date.num_days_from_ce() as u64
+    }
+
+    /// Board options of the challenge: the gameplay options are fixed, only the display
+    /// options of `base` are kept
+    pub fn board_options(&self, base: &BoardOptions) -> BoardOptions {
+        BoardOptions {
+            map_size: (16, 16),
+            bomb_count: 40,
+            safe_start: true,
+            seed: Some(self.seed()),
+            position: base.position.clone(),
+            tile_size: base.tile_size.clone(),
+            tile_padding: base.tile_padding,
+            rendering: base.rendering,
+            ..Default::default()
+        }
+    }
+}
+
+impl DailyResult {
+    /// Builds the result of a finished game
+    pub fn from_stats(stats: &GameStats) -> Option<Self> {
+        Some(Self {
+            outcome: stats.outcome?,
+            time: stats.elapsed,
+            bbbv_per_second: stats.bbbv_per_second(),
+            replays: 0,
+        })
+    }
+}
+
+impl DailyResults {
+    /// Default storage file, in the user data directory
+    pub fn default_path() -> Option<PathBuf> {
+        storage::data_file("daily_results.ron")
+    }
+
+    /// Loads the results from the default storage file
+    pub fn load() -> Self {
+        match Self::default_path() {
+            Some(path) => Self::load_from(path),
+            None => {
+                log::warn!("No user data directory, daily results won't be saved");
+                Self::default()
+            }
+        }
+    }
+
+    /// Loads the results from `path`, starting empty if the file is missing or invalid
+    pub fn load_from(path: PathBuf) -> Self {
+        let results = storage::load_ron(&path);
+        Self {
+            path: Some(path),
+            results,
+        }
+    }
+
+    /// Writes the results to their storage file
+    pub fn save(&self) -> io::Result<()> {
+        let Some(path) = &self.path else {
+            return Ok(());
+        };
+        storage::save_ron(path, &self.results)
+    }
+
+    /// Result of a date, if its challenge was played
+    pub fn get(&self, date: NaiveDate) -> Option<&DailyResult> {
+        self.results.get(&date)
+    }
+
+    /// Iterates through the results, oldest first
+    pub fn iter(&self) -> impl Iterator<Item = (NaiveDate, &DailyResult)> {
+        self.results.iter().map(|(k, v)| (*k, v))
+    }
+
+    /// Records a finished game of the challenge. Only the first attempt of a date is kept,
+    /// replays are counted as unofficial. Returns `true` if the result was official
+    pub fn record(&mut self, date: NaiveDate, stats: &GameStats) -> bool {
+        if let Some(result) = self.results.get_mut(&date) {
+            result.replays += 1;
+            return false;
+        }
+        match DailyResult::from_stats(stats) {
+            Some(result) => {
+                self.results.insert(date, result);
+                true
+            }
+            None => false,
+        }
+    }
+}
@@ -0,0 +1,409 @@
+use std::{collections::BTreeMap, io, path::PathBuf};
+
+use bevy::{log, prelude::*, utils::HashMap};
+use rand::{Rng, SeedableRng};
+use rand_chacha::ChaCha8Rng;
+use serde::{Deserialize, Serialize};
+
+use crate::component::{Chunk, Coordinates};
+
+use super::{storage, ChunkMap, Neighborhood, Tile, TileAtlas};
+
+/// Endless mode, the board extends indefinitely around the player. Must be inserted as a
+/// resource to play in the endless world
+#[derive(Debug, Clone, Copy, PartialEq, Resource)]
+pub struct EndlessMode {
+    /// Probability of a tile to be mined, for new worlds
+    pub density: f32,
+}
+
+/// One bit per tile of a chunk, one row of 32 tiles per line
+type ChunkBits = [u32; ChunkMap::CHUNK_SIZE as usize];
+
+/// Player progress on a chunk
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+struct ChunkState {
+    revealed: ChunkBits,
+    flagged: ChunkBits,
+}
+
+/// Endless world, played until the player steps on a mine.
+///
+/// Mines are generated per chunk from the world seed and the chunk coordinates, so only the
+/// player progress is stored and saved. Like boards rendered in chunks, the tiles are not
+/// entities and only the chunks around the camera are spawned
+#[derive(Debug, Clone, Resource, Serialize, Deserialize)]
+pub struct EndlessWorld {
+    seed: u64,
+    density: f32,
+    /// Progress of the chunks the player uncovered or flagged tiles in
+    chunks: BTreeMap<Chunk, ChunkState>,
+    /// Uncovered tiles count
+    score: u32,
+    /// Best score of the previous worlds
+    best_score: u32,
+    /// Tile of the mine that ended the game, if lost
+    detonated: Option<Coordinates>,
+    /// Tile at the center of the view
+    pub position: Coordinates,
+    /// Padding between tiles
+    pub tile_padding: f32,
+    /// Neighborhood rule, worlds saved before it was configurable use the square one
+    #[serde(default)]
+    neighborhood: Neighborhood,
+    /// Generated mines, by chunk
+    #[serde(skip)]
+    mines: HashMap<Chunk, ChunkBits>,
+    /// Spawned chunk entities
+    #[serde(skip)]
+    pub spawned: HashMap<Chunk, Entity>,
+    /// Do the spawned chunks need to be redrawn
+    #[serde(skip)]
+    pub dirty: bool,
+    /// Storage file, nothing is persisted if unset
+    #[serde(skip)]
+    path: Option<PathBuf>,
+}
+
+impl Default for EndlessMode {
+    fn default() -> Self {
+        Self { density: 0.18 }
+    }
+}
+
+impl EndlessWorld {
+    /// Mine density bounds. Openings of sparser worlds can spread over millions of tiles
+    pub const MIN_DENSITY: f32 = 0.12;
+    pub const MAX_DENSITY: f32 = 0.5;
+    /// Maximum number of tiles uncovered by a single opening
+    const MAX_OPENING: usize = 100_000;
+
+    /// Generates a new world, the origin and its neighbors are safe and uncovered
+    pub fn new(seed: u64, density: f32, neighborhood: Neighborhood, tile_padding: f32) -> Self {
+        let mut res = Self {
+            seed,
+            density: density.clamp(Self::MIN_DENSITY, Self::MAX_DENSITY),
+            chunks: BTreeMap::new(),
+            score: 0,
+            best_score: 0,
+            detonated: None,
+            position: Coordinates::default(),
+            tile_padding,
+            neighborhood,
+            mines: HashMap::new(),
+            spawned: HashMap::new(),
+            dirty: false,
+            path: Self::default_path(),
+        };
+        res.uncover(Coordinates::default());
+        res
+    }
+
+    /// Resumes the saved world, or generates a new one if it was lost. The best score is kept,
+    /// and a resumed world keeps its own neighborhood rule
+    pub fn load_or_new(
+        mode: &EndlessMode,
+        seed: Option<u64>,
+        neighborhood: &Neighborhood,
+        tile_padding: f32,
+    ) -> Self {
+        match Self::load() {
+            Some(world) if !world.is_lost() => {
+                log::info!("Resuming the endless world, score: {}", world.score);
+                if world.neighborhood != *neighborhood {
+                    log::warn!("The endless world keeps the neighborhood it was created with");
+                }
+                Self {
+                    tile_padding,
+                    ..world
+                }
+            }
+            saved => {
+                let seed = seed.unwrap_or_else(rand::random);
+                log::info!("New endless world, seed: {seed}");
+                // The endless world is drawn from a texture atlas, whose numbers are capped
+                let neighborhood = match neighborhood.offsets().len() {
+                    n if n > TileAtlas::MAX_NUMBER as usize => {
+                        log::warn!(
+                            "Neighborhoods of {n} tiles can't be drawn in the endless world, \
+                            falling back to the square neighborhood"
+                        );
+                        &Neighborhood::default()
+                    }
+                    _ => neighborhood,
+                };
+                Self {
+                    best_score: saved.map_or(0, |w| w.best_score()),
+                    ..Self::new(seed, mode.density, neighborhood.clone(), tile_padding)
+                }
+            }
+        }
+    }
+
+    /// Default storage file, in the user data directory
+    pub fn default_path() -> Option<PathBuf> {
+        storage::data_file("endless_world.ron")
+    }
+
+    /// Loads the saved world, if any
+    pub fn load() -> Option<Self> {
+        let Some(path) = Self::default_path() else {
+            log::warn!("No user data directory, the endless world won't be saved");
+            return None;
+        };
+        let world: Option<Self> = storage::load_ron(&path);
+        world.map(|world| Self {
+            path: Some(path),
+            ..world
+        })
+    }
+
+    /// Writes the world to its storage file
+    pub fn save(&self) -> io::Result<()> {
+        let Some(path) = &self.path else {
+            return Ok(());
+        };
+        storage::save_ron(path, &Some(self))
+    }
+
+    /// Getter for `seed`
+    pub fn seed(&self) -> u64 {
+        self.seed
+    }
+
+    /// Getter for `neighborhood`
+    pub fn neighborhood(&self) -> &Neighborhood {
+        &self.neighborhood
+    }
+
+    /// Highest possible bomb neighbor count, tiles hold a single mine
+    pub fn max_number(&self) -> u8 {
+        self.neighborhood.offsets().len().min(u8::MAX as usize) as u8
+    }
+
+    /// Number of uncovered tiles
+    pub fn score(&self) -> u32 {
+        self.score
+    }
+
+    /// Best score, this world included
+    pub fn best_score(&self) -> u32 {
+        self.best_score.max(self.score)
+    }
+
+    /// Tile of the mine the player stepped on, if lost
+    pub fn detonated(&self) -> Option<Coordinates> {
+        self.detonated
+    }
+
+    /// Did the player step on a mine
+    pub fn is_lost(&self) -> bool {
+        self.detonated.is_some()
+    }
+
+    /// Coordinates of the tiles of `chunk`
+    pub fn chunk_tiles(chunk: Chunk) -> impl Iterator<Item = Coordinates> {
+        let size = ChunkMap::CHUNK_SIZE;
+        let (x0, y0) = (chunk.x * size, chunk.y * size);
+        (y0..y0 + size).flat_map(move |y| (x0..x0 + size).map(move |x| Coordinates { x, y }))
+    }
+
+    /// Is the tile at `coords` mined, its chunk is generated if needed
+    pub fn is_mine(&mut self, coords: Coordinates) -> bool {
+        let (chunk, (x, y)) = Self::locate(coords);
+        let (seed, density, neighborhood) = (self.seed, self.density, &self.neighborhood);
+        let mines = self
+            .mines
+            .entry(chunk)
+            .or_insert_with(|| Self::generate(seed, density, neighborhood, chunk));
+        mines[y] & (1 << x) != 0
+    }
+
+    /// Number of mined neighbors of the tile at `coords`, neighboring chunks included
+    pub fn bomb_count_at(&mut self, coords: Coordinates) -> u8 {
+        (0..self.neighborhood.offsets().len())
+            .filter(|i| self.is_mine(coords + self.neighborhood.offsets()[*i]))
+            .count() as u8
+    }
+
+    /// Is the tile at `coords` uncovered
+    pub fn is_revealed(&self, coords: Coordinates) -> bool {
+        self.bit(coords, |state| &state.revealed)
+    }
+
+    /// Is the tile at `coords` flagged
+    pub fn is_flagged(&self, coords: Coordinates) -> bool {
+        self.bit(coords, |state| &state.flagged)
+    }
+
+    /// Uncovers a covered and unflagged tile, propagating through empty tiles. Revealing an
+    /// uncovered empty tile resumes the opening it stopped, if it was too large.
+    ///
+    /// Returns `true` if it changed the world
+    pub fn reveal(&mut self, coords: Coordinates) -> bool {
+        if self.is_lost() || self.is_flagged(coords) {
+            return false;
+        }
+        if self.is_revealed(coords) {
+            return self.propagate(coords);
+        }
+        if self.is_mine(coords) {
+            self.detonated = Some(coords);
+            self.best_score = self.best_score();
+            return true;
+        }
+        self.uncover(coords);
+        true
+    }
+
+    /// Toggles the flag of a covered tile, returning `true` if it changed the world
+    pub fn toggle_flag(&mut self, coords: Coordinates) -> bool {
+        if self.is_lost() || self.is_revealed(coords) {
+            return false;
+        }
+        let flagged = self.is_flagged(coords);
+        self.set_bit(coords, !flagged, |state| &mut state.flagged);
+        true
+    }
+
+    /// Uncovers the unflagged neighbors of a revealed number whose neighbor flags match its
+    /// count. Like revealing it, chording an empty tile resumes its opening.
+    ///
+    /// Returns `true` if it changed the world
+    pub fn chord(&mut self, coords: Coordinates) -> bool {
+        if self.is_lost() || !self.is_revealed(coords) {
+            return false;
+        }
+        if self.bomb_count_at(coords) == 0 {
+            return self.propagate(coords);
+        }
+        let neighbors: Vec<Coordinates> = self
+            .neighborhood
+            .offsets()
+            .iter()
+            .map(|offset| coords + *offset)
+            .collect();
+        let count = self.bomb_count_at(coords);
+        let flags = neighbors.iter().filter(|c| self.is_flagged(**c)).count();
+        if flags != count as usize {
+            return false;
+        }
+        let mut changed = false;
+        for neighbor in neighbors {
+            changed |= self.reveal(neighbor);
+        }
+        changed
+    }
+
+    /// Atlas index displaying the tile at `coords`. Every mine is revealed once the game is lost
+    pub fn tile_index(&mut self, coords: Coordinates, atlas: &TileAtlas) -> usize {
+        if self.detonated == Some(coords) {
+            TileAtlas::DETONATED
+        } else if self.is_lost() && self.is_mine(coords) {
+            TileAtlas::MINE
+        } else if self.is_flagged(coords) {
+            TileAtlas::FLAG
+        } else if !self.is_revealed(coords) {
+            TileAtlas::COVER
+        } else {
+            match self.bomb_count_at(coords) {
+                0 => atlas.tile_index(&Tile::Empty),
+                n => atlas.tile_index(&Tile::BombNeighbor(n)),
+            }
+        }
+    }
+
+    /// Uncovers a safe tile, propagating through empty tiles
+    fn uncover(&mut self, coords: Coordinates) {
+        self.set_bit(coords, true, |state| &mut state.revealed);
+        self.score += 1;
+        self.propagate(coords);
+    }
+
+    /// Uncovers the covered neighbors of the uncovered tile at `coords` if it is empty, and so
+    /// on through the empty tiles. Returns `true` if it uncovered any tile
+    fn propagate(&mut self, coords: Coordinates) -> bool {
+        let mut stack = vec![coords];
+        let mut uncovered = 0;
+        while let Some(coords) = stack.pop() {
+            if self.bomb_count_at(coords) > 0 {
+                continue;
+            }
+            for i in 0..self.neighborhood.offsets().len() {
+                let neighbor = coords + self.neighborhood.offsets()[i];
+                if self.is_revealed(neighbor) || self.is_flagged(neighbor) {
+                    continue;
+                }
+                self.set_bit(neighbor, true, |state| &mut state.revealed);
+                self.score += 1;
+                uncovered += 1;
+                stack.push(neighbor);
+            }
+            // The rest of a huge opening is resumed by revealing its empty tiles again
+            if uncovered >= Self::MAX_OPENING {
+                log::warn!("Opening stopped after {uncovered} tiles");
+                break;
+            }
+        }
+        uncovered > 0
+    }
+
+    /// Chunk of `coords`, and position of the tile in the chunk
+    fn locate(coords: Coordinates) -> (Chunk, (usize, usize)) {
+        let size = ChunkMap::CHUNK_SIZE;
+        (
+            ChunkMap::chunk_at(coords),
+            (
+                coords.x.rem_euclid(size) as usize,
+                coords.y.rem_euclid(size) as usize,
+            ),
+        )
+    }
+
+    /// Draws the mines of `chunk`, from a seed mixing the world seed and the chunk coordinates.
+    /// ChaCha is portable, so a saved world keeps its mines across platforms and updates. The
+    /// origin and its neighbors are never mined
+    fn generate(seed: u64, density: f32, neighborhood: &Neighborhood, chunk: Chunk) -> ChunkBits {
+        let chunk_seed = seed ^ (((chunk.x as u32 as u64) << 32) | chunk.y as u32 as u64);
+        // Scrambles the neighboring chunk seeds, which only differ by a bit or two
+        let chunk_seed = chunk_seed
+            .wrapping_mul(0x9E37_79B9_7F4A_7C15)
+            .rotate_left(31);
+        let mut rng = ChaCha8Rng::seed_from_u64(chunk_seed);
+        let mut res = ChunkBits::default();
+        for (row, y) in res.iter_mut().zip(chunk.y * ChunkMap::CHUNK_SIZE..) {
+            for (bit, x) in (0..ChunkMap::CHUNK_SIZE).zip(chunk.x * ChunkMap::CHUNK_SIZE..) {
+                let safe = (x, y) == (0, 0)
+                    || neighborhood
+                        .offsets()
+                        .iter()
+                        .any(|(dx, dy)| (*dx as i32, *dy as i32) == (x, y));
+                if rng.gen_bool(density as f64) && !safe {
+                    *row |= 1 << bit;
+                }
+            }
+        }
+        res
+    }
+
+    fn bit(&self, coords: Coordinates, bits: impl Fn(&ChunkState) -> &ChunkBits) -> bool {
+        let (chunk, (x, y)) = Self::locate(coords);
+        self.chunks
+            .get(&chunk)
+            .is_some_and(|state| bits(state)[y] & (1 << x) != 0)
+    }
+
+    fn set_bit(
+        &mut self,
+        coords: Coordinates,
+        value: bool,
+        bits: impl Fn(&mut ChunkState) -> &mut ChunkBits,
+    ) {
+        let (chunk, (x, y)) = Self::locate(coords);
+        let row = &mut bits(self.chunks.entry(chunk).or_default())[y];
+        match value {
+            true => *row |= 1 << x,
+            false => *row &= !(1 << x),
+        }
+    }
+}
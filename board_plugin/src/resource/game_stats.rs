@@ -0,0 +1,82 @@
+use std::time::Duration;
+
+use bevy::prelude::Resource;
+use derive_more::IsVariant;
+use serde::{Deserialize, Serialize};
+
+#[cfg(feature = "debug")]
+use bevy::prelude::{FromReflect, Reflect, ReflectResource};
+
+/// Outcome of a finished game
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize, IsVariant)]
+#[cfg_attr(feature = "debug", derive(Reflect, FromReflect))]
+pub enum GameOutcome {
+    /// Every safe tile was uncovered
+    Victory,
+    /// The player ran out of lives
+    Defeat,
+}
+
+/// Statistics of the running game, sent along with the game ending events
+#[derive(Debug, Clone, Default, Serialize, Deserialize, Resource)]
+#[cfg_attr(feature = "debug", derive(Reflect), reflect(Resource))]
+pub struct GameStats {
+    /// Remaining lives
+    pub lives_left: u8,
+    /// Number of mined tiles the player stepped on
+    pub mines_hit: u16,
+    /// Number of hints requested
+    pub hints_used: u16,
+    /// 3BV of the board, the minimum number of clicks to complete it
+    pub bbbv: u32,
+    /// Uncover clicks
+    pub left_clicks: u32,
+    /// Flag clicks
+    pub right_clicks: u32,
+    /// Chord clicks
+    pub chord_clicks: u32,
+    /// Clicks which changed the board
+    pub effective_clicks: u32,
+    /// Safe tiles uncovered
+    pub tiles_uncovered: u32,
+    /// Play time, from the first click to the end of the game
+    pub elapsed: Duration,
+    /// Game outcome, `None` while the game is running
+    pub outcome: Option<GameOutcome>,
+}
+
+impl GameStats {
+    /// Creates the statistics of a new game on a board of the given 3BV
+    pub fn new(lives: u8, bbbv: u32) -> Self {
+        Self {
+            lives_left: lives,
+            bbbv,
+            ..Default::default()
+        }
+    }
+
+    /// Total number of clicks
+    pub fn clicks(&self) -> u32 {
+        self.left_clicks + self.right_clicks + self.chord_clicks
+    }
+
+    /// 3BV solved per second
+    pub fn bbbv_per_second(&self) -> f32 {
+        self.bbbv as f32 / self.elapsed.as_secs_f32().max(f32::EPSILON)
+    }
+
+    /// Index of efficiency: 3BV per click
+    pub fn ioe(&self) -> f32 {
+        self.bbbv as f32 / self.clicks().max(1) as f32
+    }
+
+    /// Ratio of clicks which changed the board
+    pub fn correctness(&self) -> f32 {
+        self.effective_clicks as f32 / self.clicks().max(1) as f32
+    }
+
+    /// Is the game finished
+    pub fn is_finished(&self) -> bool {
+        self.outcome.is_some()
+    }
+}
@@ -0,0 +1,12 @@
+use bevy::prelude::Resource;
+
+#[cfg(feature = "debug")]
+use bevy::prelude::{Reflect, ReflectResource};
+
+/// Mine probability heatmap overlay settings, toggled at runtime
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default, Resource)]
+#[cfg_attr(feature = "debug", derive(Reflect), reflect(Resource))]
+pub struct Heatmap {
+    /// Is the overlay displayed
+    pub enabled: bool,
+}
@@ -0,0 +1,129 @@
+use std::{collections::BTreeMap, io, path::PathBuf, time::Duration};
+
+use bevy::{log, prelude::Resource};
+use chrono::{DateTime, Local};
+use serde::{Deserialize, Serialize};
+
+use super::{storage, GameStats};
+
+/// A won game entry of the high score table
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct HighScore {
+    /// Play time
+    pub time: Duration,
+    /// Date of the game
+    pub date: DateTime<Local>,
+    /// 3BV of the board
+    pub bbbv: u32,
+    /// 3BV solved per second
+    pub bbbv_per_second: f32,
+    /// Was the board completed without placing any flag
+    pub no_flags: bool,
+}
+
+/// Best games of a difficulty, sorted by time
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct DifficultyRecords {
+    /// Human readable difficulty description
+    pub label: String,
+    /// Best games, fastest first
+    pub scores: Vec<HighScore>,
+}
+
+/// Persistent high score tables, keyed by `BoardOptions::difficulty_key`
+#[derive(Debug, Clone, Default, Resource, Serialize, Deserialize)]
+pub struct HighScores {
+    /// Storage file, nothing is persisted if unset
+    #[serde(skip)]
+    path: Option<PathBuf>,
+    records: BTreeMap<String, DifficultyRecords>,
+}
+
+impl HighScore {
+    /// Builds the entry of a won game, dated now
+    pub fn from_stats(stats: &GameStats) -> Self {
+        Self {
+            time: stats.elapsed,
+            date: Local::now(),
+            bbbv: stats.bbbv,
+            bbbv_per_second: stats.bbbv_per_second(),
+            no_flags: stats.right_clicks == 0,
+        }
+    }
+}
+
+impl HighScores {
+    /// Number of entries kept per difficulty
+    pub const MAX_ENTRIES: usize = 10;
+
+    /// Default storage file, in the user data directory
+    pub fn default_path() -> Option<PathBuf> {
+        storage::data_file("high_scores.ron")
+    }
+
+    /// Loads the high scores from the default storage file
+    pub fn load() -> Self {
+        match Self::default_path() {
+            Some(path) => Self::load_from(path),
+            None => {
+                log::warn!("No user data directory, high scores won't be saved");
+                Self::default()
+            }
+        }
+    }
+
+    /// Loads the high scores from `path`, starting empty if the file is missing or invalid
+    pub fn load_from(path: PathBuf) -> Self {
+        let records = storage::load_ron(&path);
+        Self {
+            path: Some(path),
+            records,
+        }
+    }
+
+    /// Writes the high scores to their storage file
+    pub fn save(&self) -> io::Result<()> {
+        let Some(path) = &self.path else {
+            return Ok(());
+        };
+        storage::save_ron(path, &self.records)
+    }
+
+    /// Records of a difficulty, if any game was won
+    pub fn records(&self, key: &str) -> Option<&DifficultyRecords> {
+        self.records.get(key)
+    }
+
+    /// Iterates through the records of every difficulty
+    pub fn iter(&self) -> impl Iterator<Item = (&str, &DifficultyRecords)> {
+        self.records.iter().map(|(k, v)| (k.as_str(), v))
+    }
+
+    /// Best game of a difficulty
+    pub fn best(&self, key: &str) -> Option<&HighScore> {
+        self.records(key).and_then(|r| r.scores.first())
+    }
+
+    /// Inserts a won game, returning its rank if it made it to the table
+    pub fn insert(&mut self, key: &str, label: &str, score: HighScore) -> Option<usize> {
+        let records = self.records.entry(key.to_string()).or_default();
+        records.label = label.to_string();
+        let rank = records.scores.partition_point(|s| s.time <= score.time);
+        if rank >= Self::MAX_ENTRIES {
+            return None;
+        }
+        records.scores.insert(rank, score);
+        records.scores.truncate(Self::MAX_ENTRIES);
+        Some(rank)
+    }
+
+    /// Removes the records of a difficulty
+    pub fn reset(&mut self, key: &str) {
+        self.records.remove(key);
+    }
+
+    /// Removes every record
+    pub fn reset_all(&mut self) {
+        self.records.clear();
+    }
+}
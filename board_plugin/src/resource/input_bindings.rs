@@ -0,0 +1,288 @@
+use std::{collections::BTreeMap, io, path::PathBuf};
+
+use bevy::{
+    input::gamepad::GamepadButtonType,
+    log,
+    prelude::{GamepadButton, Gamepads, Input, KeyCode, MouseButton, Resource},
+};
+use serde::{Deserialize, Serialize};
+
+use super::storage;
+
+/// Logical player action, bound to physical inputs by `InputBindings`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+pub enum InputAction {
+    /// Uncovers a tile
+    Reveal,
+    /// Cycles the flags of a tile
+    Flag,
+    /// Uncovers the neighbors of a satisfied number
+    Chord,
+    /// Starts a new game
+    Restart,
+    /// Requests a hint
+    Hint,
+    /// Reserved for undo support, no system consumes it yet
+    Undo,
+    /// Pauses or resumes the game
+    Pause,
+}
+
+/// Inputs triggering an action
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Binding {
+    pub keys: Vec<KeyCode>,
+    pub mouse_buttons: Vec<MouseButton>,
+    pub gamepad_buttons: Vec<GamepadButtonType>,
+}
+
+/// Input bindings of the logical actions, persisted in the user data directory
+#[derive(Debug, Clone, Resource)]
+pub struct InputBindings {
+    /// Storage file, nothing is persisted if unset
+    path: Option<PathBuf>,
+    bindings: BTreeMap<InputAction, Binding>,
+}
+
+impl InputAction {
+    /// Every action, in display order
+    pub const ALL: [Self; 7] = [
+        Self::Reveal,
+        Self::Flag,
+        Self::Chord,
+        Self::Restart,
+        Self::Hint,
+        Self::Undo,
+        Self::Pause,
+    ];
+
+    /// Human readable action name
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::Reveal => "Reveal",
+            Self::Flag => "Flag",
+            Self::Chord => "Chord",
+            Self::Restart => "Restart",
+            Self::Hint => "Hint",
+            Self::Undo => "Undo",
+            Self::Pause => "Pause",
+        }
+    }
+}
+
+impl Binding {
+    fn new(
+        keys: &[KeyCode],
+        mouse_buttons: &[MouseButton],
+        gamepad_buttons: &[GamepadButtonType],
+    ) -> Self {
+        Self {
+            keys: keys.to_vec(),
+            mouse_buttons: mouse_buttons.to_vec(),
+            gamepad_buttons: gamepad_buttons.to_vec(),
+        }
+    }
+
+    /// Was one of the keys just pressed
+    pub fn key_just_pressed(&self, keys: &Input<KeyCode>) -> bool {
+        keys.any_just_pressed(self.keys.iter().copied())
+    }
+
+    /// Was one of the gamepad buttons just pressed on any gamepad
+    pub fn gamepad_just_pressed(
+        &self,
+        gamepads: &Gamepads,
+        gamepad_buttons: &Input<GamepadButton>,
+    ) -> bool {
+        gamepads.iter().any(|gamepad| {
+            self.gamepad_buttons
+                .iter()
+                .any(|b| gamepad_buttons.just_pressed(GamepadButton::new(gamepad, *b)))
+        })
+    }
+}
+
+impl Default for InputBindings {
+    fn default() -> Self {
+        use GamepadButtonType as Pad;
+        let bindings = [
+            (
+                InputAction::Reveal,
+                Binding::new(&[KeyCode::Space], &[MouseButton::Left], &[Pad::South]),
+            ),
+            (
+                InputAction::Flag,
+                Binding::new(&[KeyCode::F], &[MouseButton::Right], &[Pad::East]),
+            ),
+            (
+                InputAction::Chord,
+                Binding::new(&[KeyCode::C], &[MouseButton::Middle], &[Pad::West]),
+            ),
+            (
+                InputAction::Restart,
+                Binding::new(&[KeyCode::G], &[], &[Pad::Start]),
+            ),
+            (
+                InputAction::Hint,
+                Binding::new(&[KeyCode::Slash], &[], &[Pad::North]),
+            ),
+            (InputAction::Undo, Binding::default()),
+            (
+                InputAction::Pause,
+                Binding::new(&[KeyCode::Escape], &[], &[Pad::Select]),
+            ),
+        ];
+        Self {
+            path: None,
+            bindings: bindings.into_iter().collect(),
+        }
+    }
+}
+
+impl InputBindings {
+    /// Keys moving the tile cursor left, right, up and down, they can't be bound to actions
+    pub const CURSOR_KEYS: [[KeyCode; 3]; 4] = [
+        [KeyCode::Left, KeyCode::A, KeyCode::H],
+        [KeyCode::Right, KeyCode::D, KeyCode::L],
+        [KeyCode::Up, KeyCode::W, KeyCode::K],
+        [KeyCode::Down, KeyCode::S, KeyCode::J],
+    ];
+    /// Gamepad buttons moving the tile cursor left, right, up and down, they can't be bound to
+    /// actions
+    pub const CURSOR_GAMEPAD_BUTTONS: [GamepadButtonType; 4] = [
+        GamepadButtonType::DPadLeft,
+        GamepadButtonType::DPadRight,
+        GamepadButtonType::DPadUp,
+        GamepadButtonType::DPadDown,
+    ];
+    /// Key toggling the mine probability heatmap, it can't be bound to actions
+    pub const HEATMAP_KEY: KeyCode = KeyCode::P;
+    /// Key switching to the next theme, it can't be bound to actions
+    pub const THEME_KEY: KeyCode = KeyCode::T;
+    /// Key toggling the leaderboard screen, it can't be bound to actions
+    pub const LEADERBOARD_KEY: KeyCode = KeyCode::F1;
+    /// Key toggling the statistics screen, it can't be bound to actions
+    pub const STATS_KEY: KeyCode = KeyCode::F2;
+
+    /// Default storage file, in the user data directory
+    pub fn default_path() -> Option<PathBuf> {
+        storage::data_file("input_bindings.ron")
+    }
+
+    /// Loads the bindings from the default storage file
+    pub fn load() -> Self {
+        match Self::default_path() {
+            Some(path) => Self::load_from(path),
+            None => {
+                log::warn!("No user data directory, input bindings won't be saved");
+                Self::default()
+            }
+        }
+    }
+
+    /// Loads the bindings from `path`, actions missing from the file keep their default binding.
+    /// Stored bindings go through [`Self::set`], dropping reserved and conflicting inputs
+    pub fn load_from(path: PathBuf) -> Self {
+        let mut res = Self::default();
+        let stored: BTreeMap<InputAction, Binding> = storage::load_ron(&path);
+        for (action, binding) in stored {
+            res.set(action, binding);
+        }
+        res.path = Some(path);
+        res
+    }
+
+    /// Writes the bindings to their storage file
+    pub fn save(&self) -> io::Result<()> {
+        let Some(path) = &self.path else {
+            return Ok(());
+        };
+        storage::save_ron(path, &self.bindings)
+    }
+
+    /// Binding of an action
+    pub fn get(&self, action: InputAction) -> &Binding {
+        &self.bindings[&action]
+    }
+
+    /// Replaces the binding of an action. An input triggers a single action, so the inputs of
+    /// `binding` are removed from the other actions, which are returned. Reserved inputs are
+    /// left out of the binding
+    pub fn set(&mut self, action: InputAction, mut binding: Binding) -> Vec<InputAction> {
+        binding.keys.retain(|k| !Self::is_reserved_key(*k));
+        binding
+            .gamepad_buttons
+            .retain(|b| !Self::is_cursor_gamepad_button(*b));
+        let mut unbound = Vec::new();
+        for (other, other_binding) in self.bindings.iter_mut().filter(|(a, _)| **a != action) {
+            let before = other_binding.clone();
+            other_binding.keys.retain(|k| !binding.keys.contains(k));
+            other_binding
+                .mouse_buttons
+                .retain(|b| !binding.mouse_buttons.contains(b));
+            other_binding
+                .gamepad_buttons
+                .retain(|b| !binding.gamepad_buttons.contains(b));
+            if *other_binding != before {
+                unbound.push(*other);
+            }
+        }
+        self.bindings.insert(action, binding);
+        unbound
+    }
+
+    /// Is `key` reserved to the tile cursor or a board shortcut
+    pub fn is_reserved_key(key: KeyCode) -> bool {
+        let shortcuts = [
+            Self::HEATMAP_KEY,
+            Self::THEME_KEY,
+            Self::LEADERBOARD_KEY,
+            Self::STATS_KEY,
+        ];
+        Self::CURSOR_KEYS.iter().flatten().any(|k| *k == key) || shortcuts.contains(&key)
+    }
+
+    /// Is the gamepad `button` reserved to the tile cursor
+    pub fn is_cursor_gamepad_button(button: GamepadButtonType) -> bool {
+        Self::CURSOR_GAMEPAD_BUTTONS.contains(&button)
+    }
+
+    /// Restores the default bindings
+    pub fn reset(&mut self) {
+        self.bindings = Self::default().bindings;
+    }
+
+    /// Swaps the reveal and flag mouse buttons, for left-handed players
+    pub fn swap_mouse_buttons(&mut self) {
+        let reveal = self.get(InputAction::Reveal).mouse_buttons.clone();
+        let flag = self.get(InputAction::Flag).mouse_buttons.clone();
+        self.bindings
+            .get_mut(&InputAction::Flag)
+            .unwrap()
+            .mouse_buttons = reveal;
+        self.bindings
+            .get_mut(&InputAction::Reveal)
+            .unwrap()
+            .mouse_buttons = flag;
+    }
+
+    /// Action bound to a mouse button, if any
+    pub fn mouse_action(&self, button: MouseButton) -> Option<InputAction> {
+        self.bindings
+            .iter()
+            .find(|(_, b)| b.mouse_buttons.contains(&button))
+            .map(|(action, _)| *action)
+    }
+
+    /// Was a key or gamepad button of the action just pressed
+    pub fn just_pressed(
+        &self,
+        action: InputAction,
+        keys: &Input<KeyCode>,
+        gamepads: &Gamepads,
+        gamepad_buttons: &Input<GamepadButton>,
+    ) -> bool {
+        let binding = self.get(action);
+        binding.key_just_pressed(keys) || binding.gamepad_just_pressed(gamepads, gamepad_buttons)
+    }
+}
@@ -1,7 +1,21 @@
 use bevy::prelude::*;
 
+use super::BoardTheme;
+
 #[derive(Debug, Clone, Resource, PartialEq, Eq, Hash)]
 pub struct LoadedAssets {
     pub bomb_image: Handle<Image>,
+    pub flag_image: Handle<Image>,
     pub font: Handle<Font>,
 }
+
+impl LoadedAssets {
+    /// Loads the font and sprites of `theme`
+    pub fn load(theme: &BoardTheme, asset_server: &AssetServer) -> Self {
+        Self {
+            bomb_image: asset_server.load(theme.bomb_image.as_str()),
+            flag_image: asset_server.load(theme.flag_image.as_str()),
+            font: asset_server.load(theme.font.as_str()),
+        }
+    }
+}
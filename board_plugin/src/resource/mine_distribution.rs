@@ -0,0 +1,70 @@
+use rand::{distributions::WeightedIndex, prelude::Distribution, Rng};
+use serde::{Deserialize, Serialize};
+
+#[cfg(feature = "debug")]
+use bevy::prelude::{FromReflect, Reflect};
+
+/// Relative weights of the number of mines stacked on a single mined tile.
+///
+/// The first weight is for 1 mine, the second one for 2 mines, etc.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(from = "Vec<u32>")]
+#[cfg_attr(feature = "debug", derive(Reflect, FromReflect))]
+pub struct MineDistribution(Vec<u32>);
+
+impl MineDistribution {
+    /// Builds a distribution from raw weights.
+    ///
+    /// Trailing zero weights are discarded, and a distribution without any weight
+    /// falls back to the classic single mine tiles
+    pub fn new(mut weights: Vec<u32>) -> Self {
+        weights.truncate(u8::MAX as usize);
+        while weights.last() == Some(&0) {
+            weights.pop();
+        }
+        if weights.is_empty() {
+            return Self::single();
+        }
+        Self(weights)
+    }
+
+    /// Classic Minesweeper: a mined tile holds exactly one mine
+    pub fn single() -> Self {
+        Self(vec![1])
+    }
+
+    /// Every stack size from 1 to `max` is equally likely
+    pub fn uniform(max: u8) -> Self {
+        Self::new(vec![1; max as usize])
+    }
+
+    /// Getter for the weights
+    pub fn weights(&self) -> &[u32] {
+        &self.0
+    }
+
+    /// Maximum number of mines on a single tile
+    pub fn max_stack(&self) -> u8 {
+        self.0.len() as u8
+    }
+
+    /// Picks a random stack size
+    pub fn sample(&self, rng: &mut impl Rng) -> u8 {
+        match WeightedIndex::new(&self.0) {
+            Ok(index) => index.sample(rng) as u8 + 1,
+            Err(_) => 1,
+        }
+    }
+}
+
+impl Default for MineDistribution {
+    fn default() -> Self {
+        Self::single()
+    }
+}
+
+impl From<Vec<u32>> for MineDistribution {
+    fn from(weights: Vec<u32>) -> Self {
+        Self::new(weights)
+    }
+}
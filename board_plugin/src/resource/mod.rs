@@ -1,11 +1,42 @@
 mod board;
+mod board_mask;
 mod board_options;
+mod board_theme;
+mod bot_player;
+mod chunk_map;
+mod daily_challenge;
+mod endless_world;
+mod game_stats;
+mod heatmap;
+mod high_scores;
+mod input_bindings;
 mod loaded_assets;
+mod mine_distribution;
+mod neighborhood;
+mod player_stats;
+mod storage;
 mod tile;
+mod tile_atlas;
 pub mod tile_map;
+mod tile_press;
 
 pub use board::*;
+pub use board_mask::*;
 pub use board_options::*;
+pub use board_theme::*;
+pub use bot_player::*;
+pub use chunk_map::*;
+pub use daily_challenge::*;
+pub use endless_world::*;
+pub use game_stats::*;
+pub use heatmap::*;
+pub use high_scores::*;
+pub use input_bindings::*;
 pub use loaded_assets::*;
+pub use mine_distribution::*;
+pub use neighborhood::*;
+pub use player_stats::*;
 pub use tile::*;
+pub use tile_atlas::*;
 pub use tile_map::*;
+pub use tile_press::*;
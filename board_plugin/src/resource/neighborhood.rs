@@ -0,0 +1,110 @@
+use serde::{Deserialize, Serialize};
+
+#[cfg(feature = "debug")]
+use bevy::prelude::{FromReflect, Reflect};
+
+/// Neighborhood rule, defining which tiles are considered as neighbors of a tile
+/// through their delta coordinates
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(from = "Vec<(i8, i8)>", into = "Vec<(i8, i8)>")]
+#[cfg_attr(feature = "debug", derive(Reflect, FromReflect))]
+pub struct Neighborhood(Vec<(i8, i8)>);
+
+impl Neighborhood {
+    /// Delta coordinates for all 8 square neighbors
+    const SQUARE_COORDINATES: [(i8, i8); 8] = [
+        // Bottom left
+        (-1, -1),
+        // Bottom
+        (0, -1),
+        // Bottom right
+        (1, -1),
+        // Left
+        (-1, 0),
+        // Right
+        (1, 0),
+        // Top Left
+        (-1, 1),
+        // Top
+        (0, 1),
+        // Top right
+        (1, 1),
+    ];
+
+    /// Delta coordinates for all 8 knight's move neighbors
+    const KNIGHT_COORDINATES: [(i8, i8); 8] = [
+        (-1, -2),
+        (1, -2),
+        (-2, -1),
+        (2, -1),
+        (-2, 1),
+        (2, 1),
+        (-1, 2),
+        (1, 2),
+    ];
+
+    /// Builds a neighborhood from arbitrary delta coordinates.
+    ///
+    /// Duplicates and the `(0, 0)` offset are discarded, so a tile is never its own neighbor
+    pub fn from_offsets(offsets: impl IntoIterator<Item = (i8, i8)>) -> Self {
+        let mut res: Vec<(i8, i8)> = Vec::new();
+        for offset in offsets {
+            if offset != (0, 0) && !res.contains(&offset) {
+                res.push(offset);
+            }
+        }
+        Self(res)
+    }
+
+    /// Classic Minesweeper neighborhood: the 8 surrounding tiles
+    pub fn square() -> Self {
+        Self(Self::SQUARE_COORDINATES.to_vec())
+    }
+
+    /// Knight's move neighborhood: the 8 tiles a chess knight could jump to
+    pub fn knight() -> Self {
+        Self(Self::KNIGHT_COORDINATES.to_vec())
+    }
+
+    /// Square neighborhood extended to `radius` tiles in every direction
+    /// (`radius(2)` is the 5x5 square around the tile)
+    pub fn radius(radius: i8) -> Self {
+        let radius = radius.saturating_abs();
+        Self::from_offsets(
+            (-radius..=radius).flat_map(|y| (-radius..=radius).map(move |x| (x, y))),
+        )
+    }
+
+    /// Getter for the delta coordinates
+    pub fn offsets(&self) -> &[(i8, i8)] {
+        &self.0
+    }
+
+    /// Maximum number of neighbors a tile can have
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    /// Is the neighborhood empty
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+}
+
+impl Default for Neighborhood {
+    fn default() -> Self {
+        Self::square()
+    }
+}
+
+impl From<Vec<(i8, i8)>> for Neighborhood {
+    fn from(offsets: Vec<(i8, i8)>) -> Self {
+        Self::from_offsets(offsets)
+    }
+}
+
+impl From<Neighborhood> for Vec<(i8, i8)> {
+    fn from(neighborhood: Neighborhood) -> Self {
+        neighborhood.0
+    }
+}
@@ -0,0 +1,131 @@
+use std::{collections::BTreeMap, io, path::PathBuf, time::Duration};
+
+use bevy::{log, prelude::Resource};
+use serde::{Deserialize, Serialize};
+
+use super::{storage, GameOutcome, GameStats};
+
+/// Cumulative statistics of a difficulty
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DifficultyStats {
+    /// Human readable difficulty description
+    pub label: String,
+    /// Finished games
+    pub games_played: u32,
+    /// Won games
+    pub games_won: u32,
+    /// Lost games
+    pub games_lost: u32,
+    /// Consecutive wins of the ongoing streak
+    pub current_streak: u32,
+    /// Longest win streak
+    pub best_streak: u32,
+    /// Cumulated play time of the won games
+    pub total_win_time: Duration,
+    /// Safe tiles uncovered across all games
+    pub tiles_uncovered: u64,
+}
+
+/// Persistent lifetime player statistics, keyed by `BoardOptions::difficulty_key`
+#[derive(Debug, Clone, Default, Resource, Serialize, Deserialize)]
+pub struct PlayerStats {
+    /// Storage file, nothing is persisted if unset
+    #[serde(skip)]
+    path: Option<PathBuf>,
+    difficulties: BTreeMap<String, DifficultyStats>,
+}
+
+impl DifficultyStats {
+    /// Ratio of won games
+    pub fn win_rate(&self) -> f32 {
+        self.games_won as f32 / self.games_played.max(1) as f32
+    }
+
+    /// Average play time of the won games
+    pub fn average_win_time(&self) -> Option<Duration> {
+        (self.games_won > 0).then(|| self.total_win_time / self.games_won)
+    }
+
+    /// Accounts for a finished game
+    pub fn record(&mut self, stats: &GameStats) {
+        let Some(outcome) = stats.outcome else {
+            return;
+        };
+        self.games_played += 1;
+        self.tiles_uncovered += stats.tiles_uncovered as u64;
+        match outcome {
+            GameOutcome::Victory => {
+                self.games_won += 1;
+                self.current_streak += 1;
+                self.best_streak = self.best_streak.max(self.current_streak);
+                self.total_win_time += stats.elapsed;
+            }
+            GameOutcome::Defeat => {
+                self.games_lost += 1;
+                self.current_streak = 0;
+            }
+        }
+    }
+}
+
+impl PlayerStats {
+    /// Default storage file, in the user data directory
+    pub fn default_path() -> Option<PathBuf> {
+        storage::data_file("player_stats.ron")
+    }
+
+    /// Loads the statistics from the default storage file
+    pub fn load() -> Self {
+        match Self::default_path() {
+            Some(path) => Self::load_from(path),
+            None => {
+                log::warn!("No user data directory, player statistics won't be saved");
+                Self::default()
+            }
+        }
+    }
+
+    /// Loads the statistics from `path`, starting empty if the file is missing or invalid
+    pub fn load_from(path: PathBuf) -> Self {
+        let difficulties = storage::load_ron(&path);
+        Self {
+            path: Some(path),
+            difficulties,
+        }
+    }
+
+    /// Writes the statistics to their storage file
+    pub fn save(&self) -> io::Result<()> {
+        let Some(path) = &self.path else {
+            return Ok(());
+        };
+        storage::save_ron(path, &self.difficulties)
+    }
+
+    /// Statistics of a difficulty, if any game was finished
+    pub fn get(&self, key: &str) -> Option<&DifficultyStats> {
+        self.difficulties.get(key)
+    }
+
+    /// Iterates through the statistics of every difficulty
+    pub fn iter(&self) -> impl Iterator<Item = (&str, &DifficultyStats)> {
+        self.difficulties.iter().map(|(k, v)| (k.as_str(), v))
+    }
+
+    /// Accounts for a finished game of a difficulty
+    pub fn record(&mut self, key: &str, label: &str, stats: &GameStats) {
+        let difficulty = self.difficulties.entry(key.to_string()).or_default();
+        difficulty.label = label.to_string();
+        difficulty.record(stats);
+    }
+
+    /// Removes the statistics of a difficulty
+    pub fn reset(&mut self, key: &str) {
+        self.difficulties.remove(key);
+    }
+
+    /// Removes every statistic
+    pub fn reset_all(&mut self) {
+        self.difficulties.clear();
+    }
+}
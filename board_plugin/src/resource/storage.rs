@@ -0,0 +1,37 @@
+use std::{
+    fs, io,
+    path::{Path, PathBuf},
+};
+
+use bevy::log;
+use serde::{de::DeserializeOwned, Serialize};
+
+/// Path of a storage file in the user data directory
+pub(crate) fn data_file(name: &str) -> Option<PathBuf> {
+    dirs::data_dir().map(|dir| dir.join("minesweeper").join(name))
+}
+
+/// Reads a RON storage file, falling back to the default value if it is missing or invalid
+pub(crate) fn load_ron<T: DeserializeOwned + Default>(path: &Path) -> T {
+    match fs::read_to_string(path) {
+        Ok(content) => ron::from_str(&content).unwrap_or_else(|e| {
+            log::error!("Invalid storage file {}: {e}", path.display());
+            T::default()
+        }),
+        Err(e) if e.kind() == io::ErrorKind::NotFound => T::default(),
+        Err(e) => {
+            log::error!("Failed to read {}: {e}", path.display());
+            T::default()
+        }
+    }
+}
+
+/// Writes a RON storage file, creating its directory if needed
+pub(crate) fn save_ron<T: Serialize>(path: &Path, value: &T) -> io::Result<()> {
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir)?;
+    }
+    let content = ron::ser::to_string_pretty(value, Default::default())
+        .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+    fs::write(path, content)
+}
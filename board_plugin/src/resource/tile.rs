@@ -12,10 +12,12 @@ pub enum Tile {
     /// Empty tile
     #[default]
     Empty,
-    /// Is a bomb
-    Bomb,
+    /// Is a bomb, holding the number of stacked mines
+    Bomb(u8),
     /// Is a bomb neighbor
     BombNeighbor(u8),
+    /// Is out of the board shape
+    Disabled,
 }
 
 impl Tile {
@@ -24,14 +26,19 @@ impl Tile {
         format!(
             "{}",
             match self {
-                Tile::Bomb => "*".bright_red(),
+                Tile::Bomb(1) => "*".bright_red(),
+                // Stacked mines are only displayed up to 9
+                Tile::Bomb(v) => v.min(&9).to_string().bright_red().bold(),
                 Tile::BombNeighbor(v) => match v {
                     1 => "1".cyan(),
                     2 => "2".green(),
                     3 => "3".yellow(),
-                    _ => v.to_string().red(),
+                    4..=9 => v.to_string().red(),
+                    // We keep a single character per tile
+                    _ => "+".red(),
                 },
                 Tile::Empty => " ".normal(),
+                Tile::Disabled => "#".bright_black(),
             }
         )
     }
@@ -0,0 +1,212 @@
+use bevy::{
+    prelude::*,
+    render::{
+        render_resource::{Extent3d, TextureDimension, TextureFormat},
+        texture::ImageSampler,
+    },
+};
+
+use super::{BoardTheme, Tile};
+
+/// 3x5 pixel digits, one row of 3 bits per line
+const DIGITS: [[u8; 5]; 10] = [
+    [0b111, 0b101, 0b101, 0b101, 0b111],
+    [0b010, 0b110, 0b010, 0b010, 0b111],
+    [0b111, 0b001, 0b111, 0b100, 0b111],
+    [0b111, 0b001, 0b111, 0b001, 0b111],
+    [0b101, 0b101, 0b111, 0b001, 0b001],
+    [0b111, 0b100, 0b111, 0b001, 0b111],
+    [0b111, 0b100, 0b111, 0b101, 0b111],
+    [0b111, 0b001, 0b001, 0b001, 0b001],
+    [0b111, 0b101, 0b111, 0b101, 0b111],
+    [0b111, 0b101, 0b111, 0b001, 0b111],
+];
+
+/// Sprite sheet of every tile state, drawn from the board theme with pre-rendered numbers.
+///
+/// Tiles rendered from the atlas are a single sprite each, plus their cover, instead of a
+/// sprite and a text layout per tile
+#[derive(Debug, Clone, Resource)]
+pub struct TileAtlas {
+    pub texture_atlas: Handle<TextureAtlas>,
+    pub image: Handle<Image>,
+    /// Highest pre-rendered bomb neighbor count
+    max_number: u8,
+}
+
+/// RGBA pixel buffer of a single atlas cell
+struct Cell([u8; (TileAtlas::CELL * TileAtlas::CELL * 4) as usize]);
+
+impl TileAtlas {
+    pub const COVER: usize = 0;
+    pub const REVEALED: usize = 1;
+    pub const FLAG: usize = 2;
+    pub const MINE: usize = 3;
+    pub const DETONATED: usize = 4;
+    /// Highest number drawn in an atlas, larger counts are displayed as this one
+    pub const MAX_NUMBER: u8 = 99;
+    /// Cell size in pixels
+    const CELL: u32 = 32;
+
+    /// Draws the atlas of `theme`, with numbers up to `max_number` (at most `MAX_NUMBER`)
+    pub fn new(
+        theme: &BoardTheme,
+        max_number: u8,
+        images: &mut Assets<Image>,
+        atlases: &mut Assets<TextureAtlas>,
+    ) -> Self {
+        let max_number = max_number.clamp(1, Self::MAX_NUMBER);
+        let image = images.add(Self::draw(theme, max_number));
+        let texture_atlas = atlases.add(TextureAtlas::from_grid(
+            image.clone(),
+            Vec2::splat(Self::CELL as f32),
+            Self::cell_count(max_number),
+            1,
+            None,
+            None,
+        ));
+        Self {
+            texture_atlas,
+            image,
+            max_number,
+        }
+    }
+
+    /// Redraws the atlas image in place, every atlas sprite picks up the new `theme`
+    pub fn redraw(&self, theme: &BoardTheme, images: &mut Assets<Image>) {
+        images.set_untracked(&self.image, Self::draw(theme, self.max_number));
+    }
+
+    /// Atlas index of a bomb neighbor count
+    pub fn number(&self, count: u8) -> usize {
+        Self::DETONATED + count.clamp(1, self.max_number) as usize
+    }
+
+    /// Atlas index of an uncovered tile
+    pub fn tile_index(&self, tile: &Tile) -> usize {
+        match tile {
+            Tile::Bomb(_) => Self::MINE,
+            Tile::BombNeighbor(n) => self.number(*n),
+            Tile::Empty | Tile::Disabled => Self::REVEALED,
+        }
+    }
+
+    fn cell_count(max_number: u8) -> usize {
+        Self::DETONATED + 1 + max_number as usize
+    }
+
+    fn draw(theme: &BoardTheme, max_number: u8) -> Image {
+        let mut cells = vec![
+            Cell::filled(theme.cover_color),
+            Cell::filled(theme.revealed_color),
+            Cell::flag(theme.cover_color),
+            Cell::mine(theme.revealed_color),
+            Cell::mine(theme.detonated_color),
+        ];
+        cells.extend((1..=max_number).map(|n| {
+            let mut cell = Cell::filled(theme.revealed_color);
+            cell.number(n, theme.number_color(n));
+            cell
+        }));
+
+        // Cells are laid out in a single row
+        let width = Self::CELL * cells.len() as u32;
+        let row_len = (Self::CELL * 4) as usize;
+        let mut data = Vec::with_capacity((width * Self::CELL * 4) as usize);
+        for y in 0..Self::CELL as usize {
+            for cell in cells.iter() {
+                data.extend_from_slice(&cell.0[y * row_len..(y + 1) * row_len]);
+            }
+        }
+        let mut image = Image::new(
+            Extent3d {
+                width,
+                height: Self::CELL,
+                depth_or_array_layers: 1,
+            },
+            TextureDimension::D2,
+            data,
+            TextureFormat::Rgba8UnormSrgb,
+        );
+        // Pixel digits stay sharp when scaled, and cells don't bleed into each other
+        image.sampler_descriptor = ImageSampler::nearest();
+        image
+    }
+}
+
+impl Cell {
+    fn filled(color: Color) -> Self {
+        let mut cell = Self([0; (TileAtlas::CELL * TileAtlas::CELL * 4) as usize]);
+        cell.rect((0, 0), (TileAtlas::CELL, TileAtlas::CELL), color);
+        cell
+    }
+
+    /// Fills the `(width, height)` rectangle at `(x, y)`, from the top left corner
+    fn rect(&mut self, (x, y): (u32, u32), (width, height): (u32, u32), color: Color) {
+        let [r, g, b, a] = color
+            .as_rgba_f32()
+            .map(|c| (c.clamp(0., 1.) * 255.).round() as u8);
+        let max = TileAtlas::CELL;
+        for py in y.min(max)..(y + height).min(max) {
+            for px in x.min(max)..(x + width).min(max) {
+                let i = ((py * max + px) * 4) as usize;
+                self.0[i..i + 4].copy_from_slice(&[r, g, b, a]);
+            }
+        }
+    }
+
+    /// Draws `count` centered, with 3x5 digits scaled up to fit
+    fn number(&mut self, count: u8, color: Color) {
+        let digits: Vec<usize> = count
+            .to_string()
+            .bytes()
+            .map(|b| (b - b'0') as usize)
+            .collect();
+        let scale = if digits.len() > 1 { 3 } else { 4 };
+        let digit_width = 3 * scale;
+        let width = digits.len() as u32 * (digit_width + scale) - scale;
+        let left = (TileAtlas::CELL - width) / 2;
+        let top = (TileAtlas::CELL - 5 * scale) / 2;
+        for (i, digit) in digits.into_iter().enumerate() {
+            let x = left + i as u32 * (digit_width + scale);
+            for (row, bits) in DIGITS[digit].iter().enumerate() {
+                for column in 0..3 {
+                    if bits & (0b100 >> column) != 0 {
+                        let pos = (x + column * scale, top + row as u32 * scale);
+                        self.rect(pos, (scale, scale), color);
+                    }
+                }
+            }
+        }
+    }
+
+    /// Round mine with spikes and a highlight
+    fn mine(background: Color) -> Self {
+        let mut cell = Self::filled(background);
+        let center = TileAtlas::CELL as f32 / 2.;
+        for y in 0..TileAtlas::CELL {
+            for x in 0..TileAtlas::CELL {
+                let d = Vec2::new(x as f32 + 0.5 - center, y as f32 + 0.5 - center);
+                if d.length() <= 9. {
+                    cell.rect((x, y), (1, 1), Color::rgb(0.1, 0.1, 0.1));
+                }
+            }
+        }
+        cell.rect((4, 15), (24, 2), Color::rgb(0.1, 0.1, 0.1));
+        cell.rect((15, 4), (2, 24), Color::rgb(0.1, 0.1, 0.1));
+        cell.rect((12, 12), (3, 3), Color::WHITE);
+        cell
+    }
+
+    /// Red flag on a pole, over the tile cover
+    fn flag(background: Color) -> Self {
+        let mut cell = Self::filled(background);
+        cell.rect((17, 6), (2, 20), Color::BLACK);
+        cell.rect((10, 24), (15, 3), Color::BLACK);
+        for row in 0..11 {
+            let width = 10 - (row as i32 - 5).unsigned_abs() * 2;
+            cell.rect((17 - width, 6 + row), (width, 1), Color::RED);
+        }
+        cell
+    }
+}
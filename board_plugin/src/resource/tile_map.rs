@@ -1,8 +1,11 @@
 use bevy::prelude::Resource;
 use derive_more::{Deref, DerefMut};
-use rand::Rng;
+use rand::{seq::SliceRandom, Rng};
 
-use crate::{component::Coordinates, resource::Tile};
+use crate::{
+    component::Coordinates,
+    resource::{BoardMask, BoardOptions, MineDistribution, Neighborhood, Tile},
+};
 
 #[cfg(feature = "debug")]
 use bevy_inspector_egui::prelude::*;
@@ -20,6 +23,8 @@ pub struct TileMap {
     bomb_count: u16,
     height: u16,
     width: u16,
+    neighborhood: Neighborhood,
+    max_stack: u8,
     #[deref]
     #[deref_mut]
     map: Vec<Vec<Tile>>,
@@ -35,10 +40,36 @@ impl TileMap {
             bomb_count: 0,
             height,
             width,
+            neighborhood: Default::default(),
+            max_stack: 1,
             map,
         }
     }
 
+    /// Generates an empty map shaped by `mask`
+    pub fn from_mask(mask: &BoardMask) -> Self {
+        let mut res = Self::empty(mask.width(), mask.height());
+        for y in 0..res.height as i32 {
+            for x in 0..res.width as i32 {
+                if !mask.is_enabled(Coordinates { x, y }) {
+                    res[y as usize][x as usize] = Tile::Disabled;
+                }
+            }
+        }
+        res
+    }
+
+    /// Generates a mined map from the board generation options
+    pub fn generate(options: &BoardOptions, rng: &mut impl Rng) -> Self {
+        let mut res = match &options.mask {
+            Some(mask) => Self::from_mask(mask),
+            None => Self::empty(options.map_size.0, options.map_size.1),
+        };
+        res.set_neighborhood(options.neighborhood.clone());
+        res.set_bombs_with_rng(options.bomb_count, &options.mine_distribution, rng);
+        res
+    }
+
     #[cfg(feature = "debug")]
     pub fn console_output(&self) -> String {
         let mut buffer = format!(
@@ -67,87 +98,161 @@ impl TileMap {
         self.height
     }
 
-    /// Getter for `bomb_count`
+    /// Getter for `bomb_count`, the total number of mines
     pub fn bomb_count(&self) -> u16 {
         self.bomb_count
     }
 
-    /// Delta coordinates for all 8 square neighbors
-    const SQUARE_COORDINATES: [(i8, i8); 8] = [
-        // Bottom left
-        (-1, -1),
-        // Bottom
-        (0, -1),
-        // Bottom right
-        (1, -1),
-        // Left
-        (-1, 0),
-        // Right
-        (1, 0),
-        // Top Left
-        (-1, 1),
-        // Top
-        (0, 1),
-        // Top right
-        (1, 1),
-    ];
-
-    pub fn safe_square_at(&self, coordinates: Coordinates) -> impl Iterator<Item = Coordinates> {
-        impl std::ops::Add<(i8, i8)> for Coordinates {
-            type Output = Self;
-
-            fn add(self, (x, y): (i8, i8)) -> Self::Output {
-                let x = ((self.x as i16) + x as i16) as u16;
-                let y = ((self.y as i16) + y as i16) as u16;
-                Self { x, y }
-            }
-        }
+    /// Maximum number of mines stacked on a single tile
+    pub fn max_stack(&self) -> u8 {
+        self.max_stack
+    }
+
+    /// Number of enabled tiles in the map
+    pub fn tile_count(&self) -> usize {
+        self.iter()
+            .flat_map(|line| line.iter())
+            .filter(|tile| !tile.is_disabled())
+            .count()
+    }
+
+    /// Number of mined tiles in the map
+    pub fn mined_tile_count(&self) -> usize {
+        self.iter()
+            .flat_map(|line| line.iter())
+            .filter(|tile| tile.is_bomb())
+            .count()
+    }
 
-        Self::SQUARE_COORDINATES
+    /// Highest bomb neighbor count of the map
+    pub fn max_bomb_neighbor_count(&self) -> u8 {
+        self.iter()
+            .flat_map(|line| line.iter())
+            .filter_map(|tile| match tile {
+                Tile::BombNeighbor(n) => Some(*n),
+                _ => None,
+            })
+            .max()
+            .unwrap_or(0)
+    }
+
+    /// Getter for `neighborhood`
+    pub fn neighborhood(&self) -> &Neighborhood {
+        &self.neighborhood
+    }
+
+    /// Sets the neighborhood rule used to count and propagate to neighbors.
+    /// Must be called before placing bombs
+    pub fn set_neighborhood(&mut self, neighborhood: Neighborhood) {
+        self.neighborhood = neighborhood;
+    }
+
+    /// Iterates over the neighbor coordinates of `coordinates` according to the neighborhood rule.
+    ///
+    /// Coordinates are not bound checked
+    pub fn safe_square_at(
+        &self,
+        coordinates: Coordinates,
+    ) -> impl Iterator<Item = Coordinates> + '_ {
+        self.neighborhood
+            .offsets()
             .iter()
             .copied()
             .map(move |tuple| coordinates + tuple)
     }
 
-    pub fn is_bomb_at(&self, Coordinates { x, y }: Coordinates) -> bool {
-        if x >= self.width || y >= self.height {
-            return false;
+    pub fn is_bomb_at(&self, coordinates: Coordinates) -> bool {
+        self.mines_at(coordinates) > 0
+    }
+
+    /// Number of mines stacked on the tile at `coordinates`
+    pub fn mines_at(&self, Coordinates { x, y }: Coordinates) -> u8 {
+        if !(Coordinates { x, y }).is_within((self.width, self.height)) {
+            return 0;
         };
-        self.map[y as usize][x as usize].is_bomb()
+        match self.map[y as usize][x as usize] {
+            Tile::Bomb(n) => n,
+            _ => 0,
+        }
     }
 
     pub fn bomb_count_at(&self, coordinates: Coordinates) -> u8 {
         if self.is_bomb_at(coordinates) {
             return 0;
         }
-        let res = self
-            .safe_square_at(coordinates)
-            .filter(|coord| self.is_bomb_at(*coord))
-            .count();
-        res as u8
+        self.safe_square_at(coordinates)
+            .map(|coord| self.mines_at(coord))
+            .fold(0, u8::saturating_add)
     }
 
-    /// Places bombs and bomb neighbor tiles
-    pub fn set_bombs(&mut self, bomb_count: u16) {
+    /// Places bombs and bomb neighbor tiles.
+    ///
+    /// Each mined tile holds a number of mines picked from `distribution`, and
+    /// `bomb_count` is capped to what the enabled tiles can hold
+    pub fn set_bombs(&mut self, bomb_count: u16, distribution: &MineDistribution) {
+        self.set_bombs_with_rng(bomb_count, distribution, &mut rand::thread_rng());
+    }
+
+    /// Places bombs and bomb neighbor tiles like [`Self::set_bombs`], drawing from `rng`
+    /// for reproducible maps
+    pub fn set_bombs_with_rng(
+        &mut self,
+        bomb_count: u16,
+        distribution: &MineDistribution,
+        rng: &mut impl Rng,
+    ) {
+        let max_stack = distribution.max_stack();
+        let capacity = self.tile_count() * max_stack as usize;
+        let bomb_count = bomb_count.min(capacity.try_into().unwrap_or(u16::MAX));
         self.bomb_count = bomb_count;
+        self.max_stack = max_stack;
         let mut remaining_bombs = bomb_count;
-        let mut rng = rand::thread_rng();
+        let mut tiles: Vec<Coordinates> = (0..self.height as i32)
+            .flat_map(|y| (0..self.width as i32).map(move |x| Coordinates { x, y }))
+            .filter(|c| self[c.y as usize][c.x as usize] == Tile::Empty)
+            .collect();
+        tiles.shuffle(rng);
         // Place bombs
-        while remaining_bombs > 0 {
-            let (x, y) = (
-                rng.gen_range(0..self.width) as usize,
-                rng.gen_range(0..self.height) as usize,
-            );
-            if let Tile::Empty = self[y][x] {
-                self[y][x] = Tile::Bomb;
-                remaining_bombs -= 1;
+        for coords in tiles.iter() {
+            if remaining_bombs == 0 {
+                break;
             }
+            let stack = (distribution.sample(rng) as u16).min(remaining_bombs);
+            self[coords.y as usize][coords.x as usize] = Tile::Bomb(stack as u8);
+            remaining_bombs -= stack;
         }
-        // Place bomb neighbors
-        for y in 0..self.height {
-            for x in 0..self.width {
+        // If every tile is mined, we top up the existing stacks
+        for coords in tiles.iter() {
+            if remaining_bombs == 0 {
+                break;
+            }
+            if let Tile::Bomb(n) = &mut self[coords.y as usize][coords.x as usize] {
+                let added = ((max_stack - *n) as u16).min(remaining_bombs);
+                *n += added as u8;
+                remaining_bombs -= added;
+            }
+        }
+        self.set_bomb_neighbors();
+    }
+
+    /// Map with a single mine on each of `mines`, for fixed layouts
+    #[cfg(test)]
+    pub(crate) fn with_mines(width: u16, height: u16, mines: &[Coordinates]) -> Self {
+        let mut res = Self::empty(width, height);
+        for coords in mines {
+            res[coords.y as usize][coords.x as usize] = Tile::Bomb(1);
+        }
+        res.bomb_count = mines.len() as u16;
+        res.set_bomb_neighbors();
+        res
+    }
+
+    /// Places the bomb neighbor tiles around the mines
+    fn set_bomb_neighbors(&mut self) {
+        for y in 0..self.height as i32 {
+            for x in 0..self.width as i32 {
                 let coords = Coordinates { x, y };
-                if self.is_bomb_at(coords) {
+                if self.is_bomb_at(coords) || self[y as usize][x as usize].is_disabled() {
                     continue;
                 }
                 let num = self.bomb_count_at(coords);
@@ -159,4 +264,83 @@ impl TileMap {
             }
         }
     }
+
+    /// Computes the 3BV (Bechtel's Board Benchmark Value) of the map: the minimum number
+    /// of clicks needed to uncover every safe tile, counting each opening once and each
+    /// number tile out of any opening once
+    pub fn bbbv(&self) -> u32 {
+        let index = |c: Coordinates| c.y as usize * self.width as usize + c.x as usize;
+        let in_bounds = |c: &Coordinates| c.is_within((self.width, self.height));
+        let mut revealed = vec![false; self.width as usize * self.height as usize];
+        let mut visited = revealed.clone();
+        let mut res = 0;
+        for y in 0..self.height as i32 {
+            for x in 0..self.width as i32 {
+                let start = Coordinates { x, y };
+                if self[y as usize][x as usize] != Tile::Empty || visited[index(start)] {
+                    continue;
+                }
+                // Each opening is a single click
+                res += 1;
+                visited[index(start)] = true;
+                let mut stack = vec![start];
+                while let Some(coords) = stack.pop() {
+                    revealed[index(coords)] = true;
+                    for neighbor in self.safe_square_at(coords).filter(in_bounds) {
+                        revealed[index(neighbor)] = true;
+                        let tile = self[neighbor.y as usize][neighbor.x as usize];
+                        if tile == Tile::Empty && !visited[index(neighbor)] {
+                            visited[index(neighbor)] = true;
+                            stack.push(neighbor);
+                        }
+                    }
+                }
+            }
+        }
+        // Numbers out of any opening need their own click
+        for y in 0..self.height as i32 {
+            for x in 0..self.width as i32 {
+                let coords = Coordinates { x, y };
+                if self[y as usize][x as usize].is_bomb_neighbor() && !revealed[index(coords)] {
+                    res += 1;
+                }
+            }
+        }
+        res
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn bbbv_of_a_mine_free_map_is_one_opening() {
+        assert_eq!(TileMap::with_mines(4, 3, &[]).bbbv(), 1);
+    }
+
+    #[test]
+    fn bbbv_counts_isolated_numbers() {
+        // Every safe tile touches the center mine, there is no opening
+        let map = TileMap::with_mines(3, 3, &[Coordinates { x: 1, y: 1 }]);
+        assert_eq!(map.bbbv(), 8);
+    }
+
+    #[test]
+    fn bbbv_counts_each_opening_once() {
+        // Both openings reveal the numbers between the two mines
+        let mines = [Coordinates { x: 0, y: 0 }, Coordinates { x: 2, y: 2 }];
+        assert_eq!(TileMap::with_mines(3, 3, &mines).bbbv(), 2);
+        let map = TileMap::with_mines(5, 1, &[Coordinates { x: 2, y: 0 }]);
+        assert_eq!(map.bbbv(), 2);
+    }
+
+    #[test]
+    fn bbbv_mixes_openings_and_numbers() {
+        // The opening on the left reveals the numbers column next to it, the 2 between the
+        // mines is out of it
+        let mines = [Coordinates { x: 3, y: 0 }, Coordinates { x: 3, y: 2 }];
+        let map = TileMap::with_mines(4, 3, &mines);
+        assert_eq!(map.bbbv(), 2);
+    }
 }
@@ -0,0 +1,10 @@
+use crate::{component::Coordinates, resource::InputAction};
+use bevy::prelude::Resource;
+
+/// Mouse button held over a tile. Tile actions are applied on release, only if the cursor
+/// stayed on the pressed tile
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Resource)]
+pub struct TilePress {
+    /// Held action and its tile, cleared when the cursor is dragged off the tile
+    pub pressed: Option<(InputAction, Coordinates)>,
+}
@@ -0,0 +1,118 @@
+use crate::{component::Coordinates, resource::Neighborhood};
+
+/// Public knowledge about a tile, as seen by the player
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum TileView {
+    /// Covered tile
+    #[default]
+    Covered,
+    /// Covered tile marked with flags, which might be wrong
+    Flagged(u8),
+    /// Uncovered tile with its neighbor mine count
+    Revealed(u8),
+    /// Known mine stack, either exploded or uncovered
+    Mine(u8),
+    /// Tile out of the board shape
+    Disabled,
+}
+
+impl TileView {
+    /// Is the tile content unknown
+    pub fn is_unknown(&self) -> bool {
+        matches!(self, Self::Covered | Self::Flagged(_))
+    }
+}
+
+/// Public view of a board, holding only what the player can see
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BoardView {
+    width: u16,
+    height: u16,
+    /// Row major tiles, starting from the bottom left tile
+    tiles: Vec<TileView>,
+    neighborhood: Neighborhood,
+    mine_count: u16,
+    max_stack: u8,
+}
+
+impl BoardView {
+    /// Creates a fully covered board view
+    pub fn new(
+        width: u16,
+        height: u16,
+        neighborhood: Neighborhood,
+        mine_count: u16,
+        max_stack: u8,
+    ) -> Self {
+        Self {
+            width,
+            height,
+            tiles: vec![TileView::Covered; width as usize * height as usize],
+            neighborhood,
+            mine_count,
+            max_stack: max_stack.max(1),
+        }
+    }
+
+    /// Getter for `width`
+    pub fn width(&self) -> u16 {
+        self.width
+    }
+
+    /// Getter for `height`
+    pub fn height(&self) -> u16 {
+        self.height
+    }
+
+    /// Getter for `mine_count`, the total number of mines
+    pub fn mine_count(&self) -> u16 {
+        self.mine_count
+    }
+
+    /// Getter for `max_stack`, the maximum number of mines on a single tile
+    pub fn max_stack(&self) -> u8 {
+        self.max_stack
+    }
+
+    /// Retrieves a tile, out of bounds tiles are disabled
+    pub fn get(&self, Coordinates { x, y }: Coordinates) -> TileView {
+        if !(Coordinates { x, y }).is_within((self.width, self.height)) {
+            return TileView::Disabled;
+        }
+        self.tiles[y as usize * self.width as usize + x as usize]
+    }
+
+    /// Sets a tile, out of bounds coordinates are ignored
+    pub fn set(&mut self, Coordinates { x, y }: Coordinates, tile: TileView) {
+        if !(Coordinates { x, y }).is_within((self.width, self.height)) {
+            return;
+        }
+        self.tiles[y as usize * self.width as usize + x as usize] = tile;
+    }
+
+    /// Iterates over every tile coordinates
+    pub fn coordinates(&self) -> impl Iterator<Item = Coordinates> {
+        let (width, height) = (self.width, self.height);
+        (0..height as i32).flat_map(move |y| (0..width as i32).map(move |x| Coordinates { x, y }))
+    }
+
+    /// Iterates over the enabled neighbors of `coordinates`
+    pub fn neighbors(&self, coordinates: Coordinates) -> impl Iterator<Item = Coordinates> + '_ {
+        self.neighborhood
+            .offsets()
+            .iter()
+            .map(move |offset| coordinates + *offset)
+            .filter(|c| self.get(*c) != TileView::Disabled)
+    }
+
+    /// Number of mines already known, exploded or uncovered
+    pub fn known_mines(&self) -> u16 {
+        self.tiles
+            .iter()
+            .map(|tile| match tile {
+                TileView::Mine(n) => *n as u16,
+                _ => 0,
+            })
+            .sum()
+    }
+}
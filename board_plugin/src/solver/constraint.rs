@@ -0,0 +1,90 @@
+use std::collections::{BTreeMap, BTreeSet};
+
+use crate::component::Coordinates;
+
+use super::{BoardView, TileView};
+
+/// Linear constraint from a revealed number: `tiles` hold exactly `mines` mines in total
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Constraint {
+    /// Unknown tiles, sorted
+    pub tiles: Vec<Coordinates>,
+    /// Mines among `tiles`
+    pub mines: u16,
+}
+
+impl Constraint {
+    /// Builds the constraints of every revealed number bordering unknown tiles.
+    ///
+    /// Tiles of `safe` are excluded, tiles of `mines` are excluded and their mine count
+    /// is subtracted from the constraints, which requires single mine tiles
+    pub fn collect(
+        view: &BoardView,
+        safe: &BTreeSet<Coordinates>,
+        mines: &BTreeSet<Coordinates>,
+    ) -> Vec<Self> {
+        let mut res = BTreeSet::new();
+        for coords in view.coordinates() {
+            let TileView::Revealed(count) = view.get(coords) else {
+                continue;
+            };
+            let mut required = count as i32;
+            let mut tiles = Vec::new();
+            for neighbor in view.neighbors(coords) {
+                match view.get(neighbor) {
+                    TileView::Mine(n) => required -= n as i32,
+                    tile if tile.is_unknown() => {
+                        if mines.contains(&neighbor) {
+                            required -= 1;
+                        } else if !safe.contains(&neighbor) {
+                            tiles.push(neighbor);
+                        }
+                    }
+                    _ => (),
+                }
+            }
+            if tiles.is_empty() {
+                continue;
+            }
+            tiles.sort();
+            tiles.dedup();
+            res.insert(Self {
+                tiles,
+                mines: required.max(0) as u16,
+            });
+        }
+        res.into_iter().collect()
+    }
+
+    /// Is `self` a subset of `other`
+    pub fn is_subset_of(&self, other: &Self) -> bool {
+        self.tiles.len() <= other.tiles.len()
+            && self
+                .tiles
+                .iter()
+                .all(|tile| other.tiles.binary_search(tile).is_ok())
+    }
+
+    /// Constraint on the tiles of `other` which are not in `self`, `self` being a subset of `other`
+    pub fn difference(&self, other: &Self) -> Option<Self> {
+        let tiles: Vec<Coordinates> = other
+            .tiles
+            .iter()
+            .filter(|tile| self.tiles.binary_search(tile).is_err())
+            .copied()
+            .collect();
+        let mines = other.mines.checked_sub(self.mines)?;
+        (!tiles.is_empty()).then_some(Self { tiles, mines })
+    }
+
+    /// Maps every constrained tile to the indices of the constraints involving it
+    pub fn tile_index(constraints: &[Self]) -> BTreeMap<Coordinates, Vec<usize>> {
+        let mut res: BTreeMap<Coordinates, Vec<usize>> = BTreeMap::new();
+        for (i, constraint) in constraints.iter().enumerate() {
+            for tile in constraint.tiles.iter() {
+                res.entry(*tile).or_default().push(i);
+            }
+        }
+        res
+    }
+}
@@ -0,0 +1,178 @@
+use std::collections::BTreeSet;
+
+use crate::component::Coordinates;
+
+use super::{BoardView, Constraint, MineProbabilities, TileView};
+
+/// Solver answer to a hint request
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Hint {
+    /// The tile is provably safe
+    Safe(Coordinates),
+    /// The tile provably holds a mine
+    Mine(Coordinates),
+    /// No deduction exists, the tile is the least likely to hold a mine
+    Guess {
+        coordinates: Coordinates,
+        mine_probability: f32,
+    },
+}
+
+impl Hint {
+    /// Analyses the board view to find the most helpful hint, ignoring the player flags.
+    ///
+    /// Returns `None` if there is no covered tile left
+    pub fn find(view: &BoardView) -> Option<Self> {
+        let deductions = Deductions::solve(view);
+        let unmarked = |coords: &&Coordinates| view.get(**coords) == TileView::Covered;
+        if let Some(coords) = deductions.safe.iter().find(unmarked) {
+            return Some(Self::Safe(*coords));
+        }
+        if let Some(coords) = deductions.mines.iter().find(unmarked) {
+            return Some(Self::Mine(*coords));
+        }
+        MineProbabilities::compute(view)
+            .iter()
+            .filter(|(coords, _)| view.get(*coords) == TileView::Covered)
+            .min_by(|(_, a), (_, b)| a.total_cmp(b))
+            .map(|(coordinates, mine_probability)| Self::Guess {
+                coordinates,
+                mine_probability,
+            })
+    }
+
+    /// Coordinates of the hinted tile
+    pub fn coordinates(&self) -> Coordinates {
+        match self {
+            Self::Safe(c) | Self::Mine(c) => *c,
+            Self::Guess { coordinates, .. } => *coordinates,
+        }
+    }
+}
+
+/// Tiles whose content can be proven from the revealed numbers
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Deductions {
+    /// Provably safe unknown tiles
+    pub safe: BTreeSet<Coordinates>,
+    /// Unknown tiles provably holding at least a mine
+    pub mines: BTreeSet<Coordinates>,
+}
+
+impl Deductions {
+    /// Runs the single constraint and subset rules until no new deduction is found
+    pub fn solve(view: &BoardView) -> Self {
+        let mut res = Self::default();
+        loop {
+            let constraints = Constraint::collect(view, &res.safe, res.known_mines(view));
+            let mut changed = false;
+            for constraint in constraints.iter() {
+                changed |= res.apply(constraint, view.max_stack());
+            }
+            if !changed {
+                // Subset rule: if `a` is a subset of `b`, `b \ a` holds the difference of their mines
+                let index = Constraint::tile_index(&constraints);
+                for (i, a) in constraints.iter().enumerate() {
+                    let candidates: BTreeSet<usize> = a
+                        .tiles
+                        .iter()
+                        .flat_map(|tile| index[tile].iter().copied())
+                        .collect();
+                    for b in candidates.into_iter().filter(|j| *j != i) {
+                        let b = &constraints[b];
+                        if !a.is_subset_of(b) {
+                            continue;
+                        }
+                        if let Some(difference) = a.difference(b) {
+                            changed |= res.apply(&difference, view.max_stack());
+                        }
+                    }
+                }
+            }
+            if !changed {
+                return res;
+            }
+        }
+    }
+
+    /// Deduced mines whose count is exactly known, which is only the case
+    /// for single mine tiles
+    fn known_mines(&self, view: &BoardView) -> &BTreeSet<Coordinates> {
+        static EMPTY: BTreeSet<Coordinates> = BTreeSet::new();
+        match view.max_stack() {
+            1 => &self.mines,
+            _ => &EMPTY,
+        }
+    }
+
+    /// Applies the single constraint rules, returning `true` if anything new was deduced
+    fn apply(&mut self, constraint: &Constraint, max_stack: u8) -> bool {
+        let mut changed = false;
+        if constraint.mines == 0 {
+            for tile in constraint.tiles.iter() {
+                changed |= self.safe.insert(*tile);
+            }
+        // Even with every other tile full, each tile still needs a mine
+        } else if constraint.mines as usize > (constraint.tiles.len() - 1) * max_stack as usize {
+            for tile in constraint.tiles.iter() {
+                changed |= self.mines.insert(*tile);
+            }
+        }
+        changed
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::resource::Neighborhood;
+
+    /// View of a `width` by `height` board whose bottom line is revealed with `numbers`
+    fn view(width: u16, height: u16, mine_count: u16, numbers: &[u8]) -> BoardView {
+        let mut res = BoardView::new(width, height, Neighborhood::default(), mine_count, 1);
+        for (x, n) in numbers.iter().enumerate() {
+            res.set(Coordinates { x: x as i32, y: 0 }, TileView::Revealed(*n));
+        }
+        res
+    }
+
+    fn coords(tiles: &[(i32, i32)]) -> BTreeSet<Coordinates> {
+        tiles
+            .iter()
+            .map(|(x, y)| Coordinates { x: *x, y: *y })
+            .collect()
+    }
+
+    #[test]
+    fn covered_board_has_no_deduction() {
+        let deductions = Deductions::solve(&view(3, 3, 2, &[]));
+        assert!(deductions.safe.is_empty());
+        assert!(deductions.mines.is_empty());
+    }
+
+    #[test]
+    fn single_constraint_rules() {
+        // The zero clears its neighbor, the one is then left with a single covered neighbor
+        let mut view = view(4, 1, 1, &[0]);
+        view.set(Coordinates { x: 2, y: 0 }, TileView::Revealed(1));
+        let deductions = Deductions::solve(&view);
+        assert_eq!(deductions.safe, coords(&[(1, 0)]));
+        assert_eq!(deductions.mines, coords(&[(3, 0)]));
+    }
+
+    #[test]
+    fn subset_rule_solves_one_two_one() {
+        let deductions = Deductions::solve(&view(3, 2, 2, &[1, 2, 1]));
+        assert_eq!(deductions.safe, coords(&[(1, 1)]));
+        assert_eq!(deductions.mines, coords(&[(0, 1), (2, 1)]));
+    }
+
+    #[test]
+    fn flags_are_ignored() {
+        let mut view = view(3, 2, 2, &[1, 2, 1]);
+        view.set(Coordinates { x: 1, y: 1 }, TileView::Flagged(1));
+        let deductions = Deductions::solve(&view);
+        assert_eq!(deductions.safe, coords(&[(1, 1)]));
+        assert_eq!(deductions.mines, coords(&[(0, 1), (2, 1)]));
+    }
+}
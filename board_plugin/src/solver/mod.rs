@@ -0,0 +1,9 @@
+mod board_view;
+mod constraint;
+mod hint;
+mod probability;
+
+pub use board_view::*;
+pub use constraint::*;
+pub use hint::*;
+pub use probability::*;
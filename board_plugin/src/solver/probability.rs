@@ -0,0 +1,467 @@
+use std::collections::{BTreeMap, BTreeSet, VecDeque};
+
+use crate::component::Coordinates;
+
+use super::{BoardView, Constraint, Deductions};
+
+/// Maximum number of search nodes explored for a single frontier component,
+/// bounding the computation time on pathological boards
+const SEARCH_BUDGET: usize = 2_000_000;
+
+/// Mine probability of every unknown tile of a board view
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct MineProbabilities {
+    probabilities: BTreeMap<Coordinates, f32>,
+    exact: bool,
+}
+
+impl MineProbabilities {
+    /// Computes the mine probabilities from the revealed numbers and the total mine count.
+    ///
+    /// Every frontier component (unknown tiles linked by shared constraints) is enumerated,
+    /// and the components are combined with the remaining unconstrained tiles, every mine
+    /// layout being equally likely. Player flags are ignored.
+    ///
+    /// Boards with stacked mines, or components too large to enumerate, fall back to an
+    /// estimation from the constraint densities
+    pub fn compute(view: &BoardView) -> Self {
+        let deductions = Deductions::solve(view);
+        if view.max_stack() == 1 {
+            if let Some(probabilities) = Self::enumerate(view, &deductions) {
+                return Self {
+                    probabilities,
+                    exact: true,
+                };
+            }
+        }
+        Self {
+            probabilities: Self::estimate(view, &deductions),
+            exact: false,
+        }
+    }
+
+    /// Mine probability of the tile at `coordinates`, `None` if the tile is not unknown
+    pub fn get(&self, coordinates: Coordinates) -> Option<f32> {
+        self.probabilities.get(&coordinates).copied()
+    }
+
+    /// Iterates over the unknown tiles and their mine probability
+    pub fn iter(&self) -> impl Iterator<Item = (Coordinates, f32)> + '_ {
+        self.probabilities.iter().map(|(c, p)| (*c, *p))
+    }
+
+    /// Are the probabilities exact or estimated
+    pub fn is_exact(&self) -> bool {
+        self.exact
+    }
+
+    /// Exact probabilities through the enumeration of the frontier components,
+    /// for single mine tiles only
+    fn enumerate(view: &BoardView, deductions: &Deductions) -> Option<BTreeMap<Coordinates, f32>> {
+        let constraints = Constraint::collect(view, &deductions.safe, &deductions.mines);
+        let frontier: BTreeSet<Coordinates> = constraints
+            .iter()
+            .flat_map(|c| c.tiles.iter().copied())
+            .collect();
+        let components = Component::split(&constraints)
+            .into_iter()
+            .map(Component::solve)
+            .collect::<Option<Vec<_>>>()?;
+
+        let unknown: Vec<Coordinates> = view
+            .coordinates()
+            .filter(|c| view.get(*c).is_unknown())
+            .collect();
+        let remaining =
+            view.mine_count() as i64 - view.known_mines() as i64 - deductions.mines.len() as i64;
+        let outside: Vec<Coordinates> = unknown
+            .iter()
+            .filter(|c| !deductions.safe.contains(c) && !deductions.mines.contains(c))
+            .filter(|c| !frontier.contains(c))
+            .copied()
+            .collect();
+        if remaining < 0 {
+            return None;
+        }
+        let remaining = remaining as usize;
+        let outside_weights = OutsideWeights::new(outside.len(), remaining);
+
+        // Products of the component polynomials, excluding one component at a time
+        let mut prefix = vec![vec![1.]];
+        for comp in components.iter() {
+            let last = prefix.last().unwrap();
+            prefix.push(convolve(last, &comp.solutions));
+        }
+        let mut suffix = vec![vec![1.]];
+        for comp in components.iter().rev() {
+            let last = suffix.last().unwrap();
+            suffix.push(convolve(last, &comp.solutions));
+        }
+        suffix.reverse();
+
+        let total = &prefix[components.len()];
+        let weight: f64 = total
+            .iter()
+            .enumerate()
+            .map(|(s, n)| n * outside_weights.get(s))
+            .sum();
+        if weight <= 0. || !weight.is_finite() {
+            return None;
+        }
+
+        let mut res = BTreeMap::new();
+        for (i, comp) in components.iter().enumerate() {
+            let others = convolve(&prefix[i], &suffix[i + 1]);
+            // Weight of the other components and outside tiles, for each mine count of this one
+            let context: Vec<f64> = (0..comp.solutions.len())
+                .map(|k| {
+                    others
+                        .iter()
+                        .enumerate()
+                        .map(|(j, n)| n * outside_weights.get(k + j))
+                        .sum()
+                })
+                .collect();
+            for (t, tile) in comp.tiles.iter().enumerate() {
+                let p: f64 = comp
+                    .tile_mines
+                    .iter()
+                    .zip(context.iter())
+                    .map(|(counts, w)| counts[t] * w)
+                    .sum();
+                res.insert(*tile, (p / weight) as f32);
+            }
+        }
+        if !outside.is_empty() {
+            let p: f64 = total
+                .iter()
+                .enumerate()
+                .filter(|(s, _)| *s <= remaining)
+                .map(|(s, n)| n * outside_weights.get(s) * (remaining - s) as f64)
+                .sum::<f64>()
+                / outside.len() as f64
+                / weight;
+            res.extend(outside.into_iter().map(|c| (c, p as f32)));
+        }
+        res.extend(deductions.safe.iter().map(|c| (*c, 0.)));
+        res.extend(deductions.mines.iter().map(|c| (*c, 1.)));
+        Some(res)
+    }
+
+    /// Estimates the probabilities from the densities of the constraints around each tile
+    /// and the density of the rest of the board
+    fn estimate(view: &BoardView, deductions: &Deductions) -> BTreeMap<Coordinates, f32> {
+        let known_mines: BTreeSet<Coordinates> = match view.max_stack() {
+            1 => deductions.mines.clone(),
+            _ => BTreeSet::new(),
+        };
+        let constraints = Constraint::collect(view, &deductions.safe, &known_mines);
+        let mut res = BTreeMap::new();
+        for constraint in constraints.iter() {
+            let density = (constraint.mines as f32 / constraint.tiles.len() as f32).min(1.);
+            for tile in constraint.tiles.iter() {
+                let p = res.entry(*tile).or_insert(0f32);
+                *p = p.max(density);
+            }
+        }
+        let frontier_mines: f32 = res.values().sum();
+        let known_mines = view.known_mines() as f32 + known_mines.len() as f32;
+        let others: Vec<Coordinates> = view
+            .coordinates()
+            .filter(|c| view.get(*c).is_unknown())
+            .filter(|c| !deductions.safe.contains(c) && !deductions.mines.contains(c))
+            .filter(|c| !res.contains_key(c))
+            .collect();
+        let density = ((view.mine_count() as f32 - known_mines - frontier_mines)
+            / others.len().max(1) as f32)
+            .clamp(0., 1.);
+        res.extend(others.into_iter().map(|c| (c, density)));
+        res.extend(deductions.safe.iter().map(|c| (*c, 0.)));
+        res.extend(deductions.mines.iter().map(|c| (*c, 1.)));
+        res
+    }
+}
+
+/// Frontier component: unknown tiles linked by shared constraints
+struct Component {
+    tiles: Vec<Coordinates>,
+    /// Constraints as local tile indices and mine counts
+    constraints: Vec<(Vec<usize>, usize)>,
+}
+
+/// Enumeration results of a frontier component
+struct SolvedComponent {
+    tiles: Vec<Coordinates>,
+    /// Number of solutions for each mine count, scaled
+    solutions: Vec<f64>,
+    /// For each mine count, number of solutions with a mine on each tile, scaled
+    tile_mines: Vec<Vec<f64>>,
+}
+
+impl Component {
+    /// Splits the constraints into independent components
+    fn split(constraints: &[Constraint]) -> Vec<Self> {
+        let index = Constraint::tile_index(constraints);
+        let mut visited = vec![false; constraints.len()];
+        let mut res = Vec::new();
+        for start in 0..constraints.len() {
+            if visited[start] {
+                continue;
+            }
+            visited[start] = true;
+            // Breadth first traversal, so that constraints are completed early in the search
+            let mut queue = VecDeque::from([start]);
+            let mut tiles: Vec<Coordinates> = Vec::new();
+            let mut members = Vec::new();
+            while let Some(i) = queue.pop_front() {
+                members.push(i);
+                for tile in constraints[i].tiles.iter() {
+                    if !tiles.contains(tile) {
+                        tiles.push(*tile);
+                    }
+                    for j in index[tile].iter() {
+                        if !visited[*j] {
+                            visited[*j] = true;
+                            queue.push_back(*j);
+                        }
+                    }
+                }
+            }
+            let local: BTreeMap<Coordinates, usize> =
+                tiles.iter().enumerate().map(|(i, c)| (*c, i)).collect();
+            let constraints = members
+                .into_iter()
+                .map(|i| {
+                    let c = &constraints[i];
+                    (c.tiles.iter().map(|t| local[t]).collect(), c.mines as usize)
+                })
+                .collect();
+            res.push(Self { tiles, constraints });
+        }
+        res
+    }
+
+    /// Enumerates every mine layout satisfying the constraints, `None` if the
+    /// search budget is exceeded or no layout exists
+    fn solve(self) -> Option<SolvedComponent> {
+        let n = self.tiles.len();
+        let mut search = Search {
+            tile_constraints: vec![Vec::new(); n],
+            assigned: vec![0; self.constraints.len()],
+            unassigned: self.constraints.iter().map(|(t, _)| t.len()).collect(),
+            required: self.constraints.iter().map(|(_, m)| *m).collect(),
+            values: vec![false; n],
+            solutions: vec![0.; n + 1],
+            tile_mines: vec![vec![0.; n]; n + 1],
+            budget: SEARCH_BUDGET,
+        };
+        for (i, (tiles, _)) in self.constraints.iter().enumerate() {
+            for t in tiles.iter() {
+                search.tile_constraints[*t].push(i);
+            }
+        }
+        if !search.run(0, 0) {
+            return None;
+        }
+        let scale = search.solutions.iter().copied().fold(0., f64::max);
+        if scale <= 0. {
+            return None;
+        }
+        // Trailing impossible mine counts are dropped to keep the products small
+        let len = search.solutions.iter().rposition(|n| *n > 0.).unwrap_or(0) + 1;
+        search.solutions.truncate(len);
+        search.tile_mines.truncate(len);
+        Some(SolvedComponent {
+            tiles: self.tiles,
+            solutions: search.solutions.iter().map(|n| n / scale).collect(),
+            tile_mines: search
+                .tile_mines
+                .into_iter()
+                .map(|counts| counts.into_iter().map(|n| n / scale).collect())
+                .collect(),
+        })
+    }
+}
+
+/// Backtracking state of a component enumeration
+struct Search {
+    tile_constraints: Vec<Vec<usize>>,
+    assigned: Vec<usize>,
+    unassigned: Vec<usize>,
+    required: Vec<usize>,
+    values: Vec<bool>,
+    solutions: Vec<f64>,
+    tile_mines: Vec<Vec<f64>>,
+    budget: usize,
+}
+
+impl Search {
+    /// Assigns tile `i` and the following ones, returns `false` if the budget is exceeded
+    fn run(&mut self, i: usize, mines: usize) -> bool {
+        if self.budget == 0 {
+            return false;
+        }
+        self.budget -= 1;
+        if i == self.values.len() {
+            self.solutions[mines] += 1.;
+            for (t, value) in self.values.iter().enumerate() {
+                if *value {
+                    self.tile_mines[mines][t] += 1.;
+                }
+            }
+            return true;
+        }
+        for value in [false, true] {
+            let v = value as usize;
+            let valid = self.tile_constraints[i].iter().all(|c| {
+                let assigned = self.assigned[*c] + v;
+                // The other unassigned tiles must still be able to reach the required count
+                assigned <= self.required[*c] && assigned + self.unassigned[*c] > self.required[*c]
+            });
+            if !valid {
+                continue;
+            }
+            for c in self.tile_constraints[i].iter() {
+                self.assigned[*c] += v;
+                self.unassigned[*c] -= 1;
+            }
+            self.values[i] = value;
+            let completed = self.run(i + 1, mines + v);
+            for c in self.tile_constraints[i].iter() {
+                self.assigned[*c] -= v;
+                self.unassigned[*c] += 1;
+            }
+            if !completed {
+                return false;
+            }
+        }
+        self.values[i] = false;
+        true
+    }
+}
+
+/// Relative number of ways to place the remaining mines on the unconstrained tiles,
+/// for each number of mines placed on the frontier
+struct OutsideWeights {
+    /// Logarithms of the binomial coefficients, shifted by their maximum
+    ln_weights: Vec<f64>,
+}
+
+impl OutsideWeights {
+    fn new(outside: usize, remaining: usize) -> Self {
+        let ln_factorials: Vec<f64> = std::iter::once(0.)
+            .chain((1..=outside).scan(0., |acc: &mut f64, k| {
+                *acc += (k as f64).ln();
+                Some(*acc)
+            }))
+            .collect();
+        let ln_binomial =
+            |n: usize, k: usize| ln_factorials[n] - ln_factorials[k] - ln_factorials[n - k];
+        let ln_weights: Vec<f64> = (0..=remaining)
+            .map(|frontier| {
+                let k = remaining - frontier;
+                match k <= outside {
+                    true => ln_binomial(outside, k),
+                    false => f64::NEG_INFINITY,
+                }
+            })
+            .collect();
+        let max = ln_weights.iter().copied().fold(f64::NEG_INFINITY, f64::max);
+        Self {
+            ln_weights: ln_weights.into_iter().map(|w| w - max).collect(),
+        }
+    }
+
+    /// Weight when `frontier` mines are placed on the frontier
+    fn get(&self, frontier: usize) -> f64 {
+        self.ln_weights.get(frontier).map_or(0., |w| w.exp())
+    }
+}
+
+/// Product of two polynomials given by their coefficients
+fn convolve(a: &[f64], b: &[f64]) -> Vec<f64> {
+    let mut res = vec![0.; a.len() + b.len() - 1];
+    for (i, x) in a.iter().enumerate() {
+        for (j, y) in b.iter().enumerate() {
+            res[i + j] += x * y;
+        }
+    }
+    res
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{resource::Neighborhood, solver::TileView};
+
+    /// Mine probabilities from every layout of the remaining mines consistent with the view
+    fn brute_force(view: &BoardView) -> BTreeMap<Coordinates, f32> {
+        let unknown: Vec<Coordinates> = view
+            .coordinates()
+            .filter(|c| view.get(*c).is_unknown())
+            .collect();
+        let remaining = (view.mine_count() - view.known_mines()) as u32;
+        let mut counts = vec![0u32; unknown.len()];
+        let mut layouts = 0;
+        for layout in 0u32..1 << unknown.len() {
+            if layout.count_ones() != remaining {
+                continue;
+            }
+            let is_mine = |c: Coordinates| match view.get(c) {
+                TileView::Mine(_) => true,
+                _ => unknown
+                    .iter()
+                    .position(|u| *u == c)
+                    .is_some_and(|i| layout & (1 << i) != 0),
+            };
+            let consistent = view.coordinates().all(|c| match view.get(c) {
+                TileView::Revealed(n) => {
+                    view.neighbors(c).filter(|n| is_mine(*n)).count() == n as usize
+                }
+                _ => true,
+            });
+            if !consistent {
+                continue;
+            }
+            layouts += 1;
+            for (i, count) in counts.iter_mut().enumerate() {
+                *count += (layout >> i) & 1;
+            }
+        }
+        assert!(layouts > 0, "The view has no consistent layout");
+        unknown
+            .into_iter()
+            .zip(counts)
+            .map(|(c, n)| (c, n as f32 / layouts as f32))
+            .collect()
+    }
+
+    fn assert_matches_brute_force(view: &BoardView) {
+        let probabilities = MineProbabilities::compute(view);
+        assert!(probabilities.is_exact());
+        let expected = brute_force(view);
+        assert_eq!(probabilities.iter().count(), expected.len());
+        for (coords, p) in expected {
+            let computed = probabilities.get(coords).unwrap();
+            assert!((computed - p).abs() < 1e-5, "{coords}: {computed} != {p}");
+        }
+    }
+
+    #[test]
+    fn frontier_and_outside_tiles() {
+        let mut view = BoardView::new(4, 3, Neighborhood::default(), 3, 1);
+        for x in 0..3 {
+            view.set(Coordinates { x, y: 0 }, TileView::Revealed(1));
+        }
+        assert_matches_brute_force(&view);
+    }
+
+    #[test]
+    fn independent_components() {
+        let mut view = BoardView::new(5, 3, Neighborhood::default(), 4, 1);
+        view.set(Coordinates { x: 0, y: 0 }, TileView::Revealed(1));
+        view.set(Coordinates { x: 4, y: 0 }, TileView::Revealed(2));
+        view.set(Coordinates { x: 2, y: 2 }, TileView::Mine(1));
+        assert_matches_brute_force(&view);
+    }
+}
@@ -0,0 +1,32 @@
+use crate::{
+    bot::BotAction,
+    event::{TileChordEvent, TileMarkEvent, TileTriggerEvent},
+    resource::{Board, BotPlayer, GameStats},
+};
+use bevy::{log, prelude::*};
+
+pub fn bot_play(
+    time: Res<Time>,
+    board: Res<Board>,
+    stats: Res<GameStats>,
+    bot_player: Option<ResMut<BotPlayer>>,
+    mut tile_trigger_ewr: EventWriter<TileTriggerEvent>,
+    mut tile_mark_ewr: EventWriter<TileMarkEvent>,
+    mut tile_chord_ewr: EventWriter<TileChordEvent>,
+) {
+    let Some(mut bot_player) = bot_player else {
+        return;
+    };
+    if stats.is_finished() || !bot_player.timer.tick(time.delta()).just_finished() {
+        return;
+    }
+    let Some(action) = bot_player.bot.next_action(&board.view()) else {
+        return;
+    };
+    log::info!("Bot plays {action:?}");
+    match action {
+        BotAction::Reveal(coords) => tile_trigger_ewr.send(coords.into()),
+        BotAction::Flag(coords) => tile_mark_ewr.send(coords.into()),
+        BotAction::Chord(coords) => tile_chord_ewr.send(coords.into()),
+    }
+}
@@ -0,0 +1,224 @@
+use std::ops::RangeInclusive;
+
+use crate::{
+    bot::BotAction,
+    component::{Chunk, Coordinates},
+    event::{
+        BoardCompletedEvent, BombExplosionEvent, GameOverEvent, TileChordEvent, TileMarkEvent,
+        TileTriggerEvent,
+    },
+    resource::{Board, ChunkMap, GameOutcome, GameStats, TileAtlas},
+};
+use bevy::{log, prelude::*};
+
+/// Plays the tile events on a board rendered in chunks, through the rule engine
+#[allow(clippy::too_many_arguments)]
+pub fn chunked_play(
+    mut chunk_map: ResMut<ChunkMap>,
+    mut stats: ResMut<GameStats>,
+    mut tile_trigger_evr: EventReader<TileTriggerEvent>,
+    mut tile_mark_evr: EventReader<TileMarkEvent>,
+    mut tile_chord_evr: EventReader<TileChordEvent>,
+    mut bomb_explosion_ewr: EventWriter<BombExplosionEvent>,
+    mut board_completed_ewr: EventWriter<BoardCompletedEvent>,
+    mut game_over_ewr: EventWriter<GameOverEvent>,
+) {
+    let actions: Vec<BotAction> = tile_trigger_evr
+        .iter()
+        .map(|e| BotAction::Reveal(e.0))
+        .chain(tile_mark_evr.iter().map(|e| BotAction::Flag(e.0)))
+        .chain(tile_chord_evr.iter().map(|e| BotAction::Chord(e.0)))
+        .collect();
+    if actions.is_empty() {
+        return;
+    }
+
+    let map = chunk_map.as_mut();
+    for action in actions {
+        let mines_hit = map.game.stats().mines_hit;
+        if !map.game.play(action) {
+            continue;
+        }
+        map.dirty = true;
+        if map.game.stats().mines_hit == mines_hit {
+            continue;
+        }
+        let (BotAction::Reveal(coords) | BotAction::Flag(coords) | BotAction::Chord(coords)) =
+            action;
+        let lives_left = map.game.stats().lives_left;
+        // Chording can step on any neighbor
+        let exploded = std::iter::once(coords)
+            .chain(map.game.tile_map().safe_square_at(coords))
+            .find(|c| {
+                map.game.tile_map().is_bomb_at(*c)
+                    && match lives_left {
+                        0 => !map.game.is_covered(*c),
+                        _ => map.game.is_exploded(*c),
+                    }
+            })
+            .unwrap_or(coords);
+        log::info!("Boom!");
+        if lives_left == 0 {
+            map.detonated = Some(exploded);
+        }
+        bomb_explosion_ewr.send(BombExplosionEvent {
+            coordinates: exploded,
+            lives_left,
+        });
+    }
+
+    // The engine keeps the click counts, the play time is measured by `tick_game_timer` and the
+    // hints by `hint_request_handler`
+    let outcome = stats.outcome;
+    *stats = GameStats {
+        elapsed: stats.elapsed,
+        hints_used: stats.hints_used,
+        ..map.game.stats().clone()
+    };
+    match stats.outcome {
+        _ if stats.outcome == outcome => (),
+        Some(GameOutcome::Victory) => {
+            log::info!(
+                "Board completed in {:.2}s! 3BV: {}, 3BV/s: {:.2}",
+                stats.elapsed.as_secs_f32(),
+                stats.bbbv,
+                stats.bbbv_per_second()
+            );
+            board_completed_ewr.send(BoardCompletedEvent(stats.clone()));
+        }
+        Some(GameOutcome::Defeat) => {
+            log::info!("Game over");
+            game_over_ewr.send(GameOverEvent(stats.clone()));
+        }
+        None => (),
+    }
+}
+
+/// Spawns the chunks in view of the camera, despawns the others, and redraws the spawned
+/// chunks when the game state changed
+pub fn update_chunks(
+    mut commands: Commands,
+    board: Res<Board>,
+    atlas: Res<TileAtlas>,
+    mut chunk_map: ResMut<ChunkMap>,
+    cameras: Query<(&Camera, &GlobalTransform), With<Camera2d>>,
+    mut tiles: Query<(&Coordinates, &mut TextureAtlasSprite)>,
+) {
+    let Some((x_range, y_range)) = visible_chunks(&board, &cameras) else {
+        return;
+    };
+    let last = ChunkMap::chunk_at(Coordinates {
+        x: board.tile_map.width() as i32 - 1,
+        y: board.tile_map.height() as i32 - 1,
+    });
+    let x_range = *x_range.start().max(&0)..=*x_range.end().min(&last.x);
+    let y_range = *y_range.start().max(&0)..=*y_range.end().min(&last.y);
+
+    let visible = |chunk: &Chunk| x_range.contains(&chunk.x) && y_range.contains(&chunk.y);
+    chunk_map.chunks.retain(|chunk, entity| {
+        let keep = visible(chunk);
+        if !keep {
+            commands.entity(*entity).despawn_recursive();
+        }
+        keep
+    });
+
+    // Spawned chunks are up to date
+    if chunk_map.dirty {
+        for (coords, mut sprite) in tiles.iter_mut() {
+            let index = chunk_map.tile_index(*coords, &atlas);
+            if sprite.index != index {
+                sprite.index = index;
+            }
+        }
+        chunk_map.dirty = false;
+    }
+
+    let Some(board_entity) = board.entity else {
+        return;
+    };
+    for y in y_range {
+        for x in x_range.clone() {
+            let chunk = Chunk { x, y };
+            if chunk_map.chunks.contains_key(&chunk) {
+                continue;
+            }
+            let tiles = chunk_map
+                .chunk_tiles(chunk)
+                .map(|coords| (coords, chunk_map.tile_index(coords, &atlas)));
+            let entity = spawn_chunk(
+                &mut commands,
+                &board,
+                &atlas,
+                chunk_map.tile_padding,
+                chunk,
+                tiles,
+            );
+            commands.entity(board_entity).add_child(entity);
+            chunk_map.chunks.insert(chunk, entity);
+        }
+    }
+}
+
+/// Chunk columns and rows in view of the camera, with a chunk of margin to spawn them before
+/// they show up
+pub(crate) fn visible_chunks(
+    board: &Board,
+    cameras: &Query<(&Camera, &GlobalTransform), With<Camera2d>>,
+) -> Option<(RangeInclusive<i32>, RangeInclusive<i32>)> {
+    let (camera, transform) = cameras.iter().next()?;
+    let viewport = camera.logical_viewport_size()?;
+    let a = camera.viewport_to_world(transform, Vec2::ZERO)?.origin;
+    let b = camera.viewport_to_world(transform, viewport)?.origin;
+
+    let chunk_size = ChunkMap::CHUNK_SIZE as f32 * board.tile_size;
+    let min = (a.truncate().min(b.truncate()) - board.bounds.min) / chunk_size;
+    let max = (a.truncate().max(b.truncate()) - board.bounds.min) / chunk_size;
+    Some((
+        min.x.floor() as i32 - 1..=max.x.floor() as i32 + 1,
+        min.y.floor() as i32 - 1..=max.y.floor() as i32 + 1,
+    ))
+}
+
+/// Spawns a chunk and its tiles, given with their atlas index
+pub(crate) fn spawn_chunk(
+    commands: &mut Commands,
+    board: &Board,
+    atlas: &TileAtlas,
+    tile_padding: f32,
+    chunk: Chunk,
+    tiles: impl Iterator<Item = (Coordinates, usize)>,
+) -> Entity {
+    let size = board.tile_size;
+    let origin = Vec2::new(chunk.x as f32, chunk.y as f32) * ChunkMap::CHUNK_SIZE as f32 * size;
+    let tiles: Vec<_> = tiles.collect();
+    commands
+        .spawn(SpatialBundle::from_transform(Transform::from_xyz(
+            origin.x, origin.y, 1.,
+        )))
+        .insert(Name::new(format!("Chunk ({}, {})", chunk.x, chunk.y)))
+        .insert(chunk)
+        .with_children(|parent| {
+            for (coords, index) in tiles {
+                let x = coords.x.rem_euclid(ChunkMap::CHUNK_SIZE) as f32;
+                let y = coords.y.rem_euclid(ChunkMap::CHUNK_SIZE) as f32;
+                parent
+                    .spawn(SpriteSheetBundle {
+                        sprite: TextureAtlasSprite {
+                            index,
+                            custom_size: Some(Vec2::splat(size - tile_padding)),
+                            ..Default::default()
+                        },
+                        texture_atlas: atlas.texture_atlas.clone(),
+                        transform: Transform::from_xyz(
+                            x * size + size / 2.,
+                            y * size + size / 2.,
+                            0.,
+                        ),
+                        ..Default::default()
+                    })
+                    .insert(coords);
+            }
+        })
+        .id()
+}
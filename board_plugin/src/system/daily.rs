@@ -0,0 +1,34 @@
+use crate::{
+    event::{BoardCompletedEvent, GameOverEvent},
+    resource::{DailyChallenge, DailyResults},
+};
+use bevy::{log, prelude::*};
+
+/// Stores the result of the first attempt of the daily challenge
+pub fn record_daily_result(
+    daily: Option<ResMut<DailyChallenge>>,
+    mut daily_results: ResMut<DailyResults>,
+    mut board_completed_evr: EventReader<BoardCompletedEvent>,
+    mut game_over_evr: EventReader<GameOverEvent>,
+) {
+    let Some(mut daily) = daily else {
+        board_completed_evr.clear();
+        game_over_evr.clear();
+        return;
+    };
+    let finished = board_completed_evr
+        .iter()
+        .map(|BoardCompletedEvent(stats)| stats)
+        .chain(game_over_evr.iter().map(|GameOverEvent(stats)| stats));
+    for stats in finished {
+        if daily_results.record(daily.date, stats) {
+            log::info!("Daily challenge {} result recorded", daily.date);
+        } else {
+            log::info!("Unofficial replay of the daily challenge {}", daily.date);
+        }
+        daily.official = false;
+        if let Err(e) = daily_results.save() {
+            log::error!("Failed to save daily results: {e}");
+        }
+    }
+}
@@ -0,0 +1,177 @@
+use crate::{
+    component::{Chunk, Coordinates, TileCursor},
+    event::{BombExplosionEvent, GameOverEvent, TileChordEvent, TileMarkEvent, TileTriggerEvent},
+    resource::{Board, EndlessWorld, GameOutcome, GameStats, TileAtlas},
+    system::chunk::{spawn_chunk, visible_chunks},
+};
+use bevy::{log, prelude::*};
+
+/// Time between two saves of the endless world, in seconds
+const AUTOSAVE_PERIOD: f32 = 30.;
+
+/// Plays the tile events in the endless world, the game ends on the first mine
+#[allow(clippy::too_many_arguments)]
+pub fn endless_play(
+    mut world: ResMut<EndlessWorld>,
+    mut stats: ResMut<GameStats>,
+    mut tile_trigger_evr: EventReader<TileTriggerEvent>,
+    mut tile_mark_evr: EventReader<TileMarkEvent>,
+    mut tile_chord_evr: EventReader<TileChordEvent>,
+    mut bomb_explosion_ewr: EventWriter<BombExplosionEvent>,
+    mut game_over_ewr: EventWriter<GameOverEvent>,
+) {
+    let mut changed = 0;
+    for event in tile_trigger_evr.iter() {
+        stats.left_clicks += 1;
+        changed += world.reveal(event.0) as u32;
+    }
+    for event in tile_mark_evr.iter() {
+        stats.right_clicks += 1;
+        changed += world.toggle_flag(event.0) as u32;
+    }
+    for event in tile_chord_evr.iter() {
+        stats.chord_clicks += 1;
+        changed += world.chord(event.0) as u32;
+    }
+    if changed == 0 {
+        return;
+    }
+    world.dirty = true;
+    stats.effective_clicks += changed;
+    stats.tiles_uncovered = world.score();
+
+    let Some(coordinates) = world.detonated() else {
+        return;
+    };
+    log::info!("Boom!");
+    stats.mines_hit += 1;
+    stats.lives_left = 0;
+    stats.outcome = Some(GameOutcome::Defeat);
+    bomb_explosion_ewr.send(BombExplosionEvent {
+        coordinates,
+        lives_left: 0,
+    });
+    log::info!(
+        "Game over, score: {} (best: {})",
+        world.score(),
+        world.best_score()
+    );
+    game_over_ewr.send(GameOverEvent(stats.clone()));
+    if let Err(e) = world.save() {
+        log::error!("Failed to save the endless world: {e}");
+    }
+}
+
+/// Spawns the chunks of the endless world in view of the camera, despawns the others, and
+/// redraws the spawned chunks when the world changed. The tile at the center of the view is
+/// kept as the player position
+pub fn update_endless_chunks(
+    mut commands: Commands,
+    board: Res<Board>,
+    atlas: Res<TileAtlas>,
+    mut world: ResMut<EndlessWorld>,
+    cameras: Query<(&Camera, &GlobalTransform), With<Camera2d>>,
+    mut tiles: Query<(&Coordinates, &mut TextureAtlasSprite)>,
+) {
+    let world = world.as_mut();
+    if let Some((_, transform)) = cameras.iter().next() {
+        let center = (transform.translation().truncate() - board.bounds.min) / board.tile_size;
+        let position = Coordinates {
+            x: center.x.floor() as i32,
+            y: center.y.floor() as i32,
+        };
+        if world.position != position {
+            world.position = position;
+        }
+    }
+    let Some((x_range, y_range)) = visible_chunks(&board, &cameras) else {
+        return;
+    };
+
+    let visible = |chunk: &Chunk| x_range.contains(&chunk.x) && y_range.contains(&chunk.y);
+    world.spawned.retain(|chunk, entity| {
+        let keep = visible(chunk);
+        if !keep {
+            commands.entity(*entity).despawn_recursive();
+        }
+        keep
+    });
+
+    // Spawned chunks are up to date
+    if world.dirty {
+        for (coords, mut sprite) in tiles.iter_mut() {
+            let index = world.tile_index(*coords, &atlas);
+            if sprite.index != index {
+                sprite.index = index;
+            }
+        }
+        world.dirty = false;
+    }
+
+    let Some(board_entity) = board.entity else {
+        return;
+    };
+    for y in y_range {
+        for x in x_range.clone() {
+            let chunk = Chunk { x, y };
+            if world.spawned.contains_key(&chunk) {
+                continue;
+            }
+            let tiles: Vec<_> = EndlessWorld::chunk_tiles(chunk)
+                .map(|coords| (coords, world.tile_index(coords, &atlas)))
+                .collect();
+            let entity = spawn_chunk(
+                &mut commands,
+                &board,
+                &atlas,
+                world.tile_padding,
+                chunk,
+                tiles.into_iter(),
+            );
+            commands.entity(board_entity).add_child(entity);
+            world.spawned.insert(chunk, entity);
+        }
+    }
+}
+
+/// Keeps the tile cursor in view of the endless world: the camera follows the cursor once it is
+/// shown, and the hidden cursor waits at the center of the view
+pub fn follow_tile_cursor(
+    board: Res<Board>,
+    world: Res<EndlessWorld>,
+    mut cursors: Query<(&mut TileCursor, &mut Transform, &Visibility), Without<Camera2d>>,
+    mut cameras: Query<&mut Transform, With<Camera2d>>,
+) {
+    for (mut cursor, mut transform, visibility) in cursors.iter_mut() {
+        if !visibility.is_visible {
+            if cursor.coordinates != world.position {
+                cursor.coordinates = world.position;
+                transform.translation = cursor.translation(board.tile_size);
+            }
+            continue;
+        }
+        if !cursor.is_changed() {
+            continue;
+        }
+        for mut camera in cameras.iter_mut() {
+            camera.translation.x = board.bounds.min.x + transform.translation.x;
+            camera.translation.y = board.bounds.min.y + transform.translation.y;
+        }
+    }
+}
+
+/// Saves the endless world periodically, on top of the saves when the game ends
+pub fn autosave_endless_world(
+    time: Res<Time>,
+    mut last_save: Local<f32>,
+    world: Res<EndlessWorld>,
+) {
+    let now = time.elapsed_seconds();
+    if now - *last_save < AUTOSAVE_PERIOD || world.is_lost() {
+        return;
+    }
+    *last_save = now;
+    if let Err(e) = world.save() {
+        log::error!("Failed to save the endless world: {e}");
+    }
+}
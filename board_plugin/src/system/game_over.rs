@@ -0,0 +1,95 @@
+use crate::{
+    component::Coordinates,
+    event::BombExplosionEvent,
+    resource::{Board, BoardTheme, LoadedAssets, TileAtlas},
+    system::mark::cover_size,
+};
+use bevy::{log, prelude::*};
+
+/// Loss sequence: uncovers every mine, highlights the detonated one and crosses out wrong flags.
+/// Covers of the other tiles stay in place
+#[allow(clippy::too_many_arguments)]
+pub fn reveal_mines(
+    mut commands: Commands,
+    mut board: ResMut<Board>,
+    loaded_assets: Res<LoadedAssets>,
+    theme: Res<BoardTheme>,
+    mut tiles: Query<(&Coordinates, &mut Sprite)>,
+    mut atlas_tiles: Query<(&Coordinates, &mut TextureAtlasSprite)>,
+    covers: Query<&Sprite, Without<Coordinates>>,
+    mut bomb_explosion_evr: EventReader<BombExplosionEvent>,
+) {
+    let Some(detonated) = bomb_explosion_evr
+        .iter()
+        .find(|event| event.lives_left == 0)
+        .map(|event| event.coordinates)
+    else {
+        return;
+    };
+    log::info!("Revealing mines, detonated on {detonated}");
+
+    for (coords, mut sprite) in tiles.iter_mut() {
+        if *coords == detonated {
+            sprite.color = theme.detonated_color;
+        }
+    }
+    for (coords, mut sprite) in atlas_tiles.iter_mut() {
+        if *coords == detonated {
+            sprite.index = TileAtlas::DETONATED;
+        }
+    }
+
+    // Wrongly flagged tiles, holding more flags than mines, get a crossed-out mine instead of
+    // their flags
+    let wrong_flags: Vec<(Coordinates, Entity)> = board
+        .marked_tiles
+        .iter()
+        .filter(|(coords, flags)| **flags > board.tile_map.mines_at(**coords))
+        .filter_map(|(coords, _)| Some((*coords, *board.covered_tiles.get(coords)?)))
+        .collect();
+    for (coords, entity) in wrong_flags.iter().copied() {
+        log::debug!("Wrong flag on {coords}");
+        let size = cover_size(&covers, entity, &board);
+        commands.entity(entity).despawn_descendants();
+        commands.entity(entity).with_children(|parent| {
+            parent
+                .spawn(SpriteBundle {
+                    sprite: Sprite {
+                        custom_size: Some(Vec2::splat(size)),
+                        ..Default::default()
+                    },
+                    texture: loaded_assets.bomb_image.clone(),
+                    transform: Transform::from_xyz(0., 0., 1.),
+                    ..Default::default()
+                })
+                .insert(Name::new("Wrong Flag"))
+                .with_children(|parent| {
+                    // The mine is crossed out with two red bars
+                    for angle in [std::f32::consts::FRAC_PI_4, -std::f32::consts::FRAC_PI_4] {
+                        parent.spawn(SpriteBundle {
+                            sprite: Sprite {
+                                color: Color::RED,
+                                custom_size: Some(Vec2::new(size * 1.2, size / 8.)),
+                                ..Default::default()
+                            },
+                            transform: Transform::from_xyz(0., 0., 1.)
+                                .with_rotation(Quat::from_rotation_z(angle)),
+                            ..Default::default()
+                        });
+                    }
+                });
+        });
+    }
+
+    // Every mine is uncovered, except the over-flagged ones which keep their cross
+    let mines: Vec<Coordinates> = (0..board.tile_map.height() as i32)
+        .flat_map(|y| (0..board.tile_map.width() as i32).map(move |x| Coordinates { x, y }))
+        .filter(|coords| board.tile_map.is_bomb_at(*coords))
+        .filter(|coords| !wrong_flags.iter().any(|(c, _)| c == coords))
+        .collect();
+    for coords in mines {
+        if let Some(entity) = board.try_uncover_tile(&coords) {
+            commands.entity(entity).despawn_recursive();
+        }
+    }
+}
@@ -0,0 +1,80 @@
+use std::time::Duration;
+
+use crate::{
+    resource::{InputAction, InputBindings},
+    system::input::{CursorCommand, CursorControl},
+};
+use bevy::prelude::*;
+
+/// Delay before a held direction starts repeating
+const REPEAT_DELAY: Duration = Duration::from_millis(300);
+/// Delay between two repeated moves of a held direction
+const REPEAT_INTERVAL: Duration = Duration::from_millis(100);
+/// Stick deflection counted as a direction
+const STICK_THRESHOLD: f32 = 0.5;
+
+/// Held direction of the gamepads
+#[derive(Debug, Default)]
+pub struct DirectionRepeat {
+    direction: (i32, i32),
+    timer: Timer,
+}
+
+/// Gamepad play: the D-pad or left stick moves the tile cursor, repeating while held.
+/// The reveal, flag and chord bound buttons play on the selected tile
+pub fn gamepad_input_handling(
+    time: Res<Time>,
+    gamepads: Res<Gamepads>,
+    buttons: Res<Input<GamepadButton>>,
+    axes: Res<Axis<GamepadAxis>>,
+    bindings: Res<InputBindings>,
+    mut repeat: Local<DirectionRepeat>,
+    mut cursor_control: CursorControl,
+) {
+    let [left, right, up, down] = InputBindings::CURSOR_GAMEPAD_BUTTONS;
+    let mut direction = (0, 0);
+    let mut command = CursorCommand::default();
+    for gamepad in gamepads.iter() {
+        let pressed = |button_type| buttons.pressed(GamepadButton::new(gamepad, button_type));
+        let axis = |axis_type| {
+            axes.get(GamepadAxis::new(gamepad, axis_type))
+                .unwrap_or_default()
+        };
+
+        let (x, y) = (
+            axis(GamepadAxisType::LeftStickX),
+            axis(GamepadAxisType::LeftStickY),
+        );
+        if pressed(left) || x < -STICK_THRESHOLD {
+            direction.0 = -1;
+        }
+        if pressed(right) || x > STICK_THRESHOLD {
+            direction.0 = 1;
+        }
+        if pressed(up) || y > STICK_THRESHOLD {
+            direction.1 = 1;
+        }
+        if pressed(down) || y < -STICK_THRESHOLD {
+            direction.1 = -1;
+        }
+    }
+    let just_pressed = |action| {
+        bindings
+            .get(action)
+            .gamepad_just_pressed(&gamepads, &buttons)
+    };
+    command.reveal = just_pressed(InputAction::Reveal);
+    command.flag = just_pressed(InputAction::Flag);
+    command.chord = just_pressed(InputAction::Chord);
+
+    // A new direction moves at once, then repeats after a delay while it is held
+    if direction != repeat.direction {
+        repeat.direction = direction;
+        repeat.timer = Timer::new(REPEAT_DELAY, TimerMode::Once);
+        command.delta = direction;
+    } else if direction != (0, 0) && repeat.timer.tick(time.delta()).just_finished() {
+        repeat.timer = Timer::new(REPEAT_INTERVAL, TimerMode::Once);
+        command.delta = direction;
+    }
+    cursor_control.apply(command);
+}
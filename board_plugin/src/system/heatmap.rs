@@ -0,0 +1,89 @@
+use crate::{
+    component::{Coordinates, HeatmapTile},
+    resource::{Board, Heatmap, LoadedAssets},
+    solver::MineProbabilities,
+    system::mark::cover_size,
+};
+use bevy::{
+    log,
+    prelude::*,
+    tasks::{AsyncComputeTaskPool, Task},
+};
+use futures_lite::future;
+
+/// Displays the mine probabilities over the covered tiles. They are computed in the background
+/// when the overlay is enabled and the board changes, the outdated overlay being cleared meanwhile
+#[allow(clippy::too_many_arguments)]
+pub fn update_heatmap(
+    mut commands: Commands,
+    board: Res<Board>,
+    heatmap: Res<Heatmap>,
+    loaded_assets: Res<LoadedAssets>,
+    tiles: Query<(Entity, &Coordinates)>,
+    covers: Query<&Sprite>,
+    overlays: Query<Entity, With<HeatmapTile>>,
+    mut task: Local<Option<Task<MineProbabilities>>>,
+) {
+    if heatmap.is_changed() || board.is_changed() {
+        for entity in overlays.iter() {
+            commands.entity(entity).despawn_recursive();
+        }
+        // Dropping the pending computation cancels it
+        *task = heatmap.enabled.then(|| {
+            let view = board.view();
+            AsyncComputeTaskPool::get().spawn(async move { MineProbabilities::compute(&view) })
+        });
+    }
+    let Some(probabilities) = task
+        .as_mut()
+        .and_then(|t| future::block_on(future::poll_once(t)))
+    else {
+        return;
+    };
+    *task = None;
+
+    if !probabilities.is_exact() {
+        log::warn!("Mine probabilities could not be enumerated, displaying estimations");
+    }
+    for (entity, coords) in tiles.iter() {
+        let (Some(p), Some(cover)) = (probabilities.get(*coords), board.covered_tiles.get(coords))
+        else {
+            continue;
+        };
+        let size = cover_size(&covers, *cover, &board);
+        // Overlays are tile children, drawn between the cover and its flags
+        commands.entity(entity).with_children(|parent| {
+            parent
+                .spawn(SpriteBundle {
+                    sprite: Sprite {
+                        color: Color::rgba(p, 1. - p, 0., 0.5),
+                        custom_size: Some(Vec2::splat(size)),
+                        ..Default::default()
+                    },
+                    transform: Transform::from_xyz(0., 0., 3.5),
+                    ..Default::default()
+                })
+                .insert(Name::new("Heatmap"))
+                .insert(HeatmapTile)
+                .with_children(|parent| {
+                    // Estimations are marked, so they are not taken for exact probabilities
+                    if probabilities.is_exact() {
+                        return;
+                    }
+                    parent.spawn(Text2dBundle {
+                        text: Text::from_section(
+                            "~",
+                            TextStyle {
+                                font: loaded_assets.font.clone(),
+                                font_size: size / 2.,
+                                color: Color::BLACK,
+                            },
+                        )
+                        .with_alignment(TextAlignment::CENTER),
+                        transform: Transform::from_xyz(0., 0., 0.1),
+                        ..Default::default()
+                    });
+                });
+        });
+    }
+}
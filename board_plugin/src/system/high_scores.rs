@@ -0,0 +1,122 @@
+use crate::{
+    component::Leaderboard,
+    event::BoardCompletedEvent,
+    resource::{
+        BoardOptions, BotPlayer, DailyChallenge, HighScore, HighScores, InputBindings, LoadedAssets,
+    },
+};
+use bevy::{log, prelude::*};
+
+/// Records won games in the high score table of their difficulty. Daily challenges are kept
+/// in their own results, games won by a bot or with hints are not recorded
+pub fn record_high_score(
+    board_options: Option<Res<BoardOptions>>,
+    daily: Option<Res<DailyChallenge>>,
+    bot_player: Option<Res<BotPlayer>>,
+    mut high_scores: ResMut<HighScores>,
+    mut board_completed_evr: EventReader<BoardCompletedEvent>,
+) {
+    if daily.is_some() || bot_player.is_some() {
+        board_completed_evr.clear();
+        return;
+    }
+    let board_options = board_options.map(|o| o.clone()).unwrap_or_default();
+    for BoardCompletedEvent(stats) in board_completed_evr.iter() {
+        if stats.hints_used > 0 {
+            log::info!("Hints used, the game is not recorded in the high scores");
+            continue;
+        }
+        let key = board_options.difficulty_key();
+        let label = board_options.difficulty_label();
+        let Some(rank) = high_scores.insert(&key, &label, HighScore::from_stats(stats)) else {
+            continue;
+        };
+        log::info!("New high score! Rank #{} for {label}", rank + 1);
+        if let Err(e) = high_scores.save() {
+            log::error!("Failed to save high scores: {e}");
+        }
+    }
+}
+
+/// Toggles the leaderboard screen of the current difficulty with F1
+pub fn leaderboard_input_handling(
+    mut commands: Commands,
+    keys: Res<Input<KeyCode>>,
+    board_options: Option<Res<BoardOptions>>,
+    high_scores: Res<HighScores>,
+    loaded_assets: Res<LoadedAssets>,
+    leaderboards: Query<Entity, With<Leaderboard>>,
+) {
+    if !keys.just_pressed(InputBindings::LEADERBOARD_KEY) {
+        return;
+    }
+    if !leaderboards.is_empty() {
+        for entity in leaderboards.iter() {
+            commands.entity(entity).despawn_recursive();
+        }
+        return;
+    }
+    let board_options = board_options.map(|o| o.clone()).unwrap_or_default();
+    let records = high_scores.records(&board_options.difficulty_key());
+    let mut content = format!("High scores - {}\n\n", board_options.difficulty_label());
+    match records {
+        Some(records) if !records.scores.is_empty() => {
+            for (rank, score) in records.scores.iter().enumerate() {
+                content += &format!(
+                    "{:>2}. {:>7.2}s  {:>5.2} 3BV/s  {}{}\n",
+                    rank + 1,
+                    score.time.as_secs_f32(),
+                    score.bbbv_per_second,
+                    score.date.format("%Y-%m-%d"),
+                    if score.no_flags { "  NF" } else { "" },
+                );
+            }
+        }
+        _ => content += "No record yet\n",
+    }
+    spawn_text_panel(
+        &mut commands,
+        Leaderboard,
+        "Leaderboard",
+        content,
+        &loaded_assets,
+    );
+}
+
+/// Spawns a text screen over the board, tagged with `marker`
+pub(crate) fn spawn_text_panel(
+    commands: &mut Commands,
+    marker: impl Component,
+    name: &'static str,
+    content: String,
+    loaded_assets: &LoadedAssets,
+) {
+    commands
+        .spawn(NodeBundle {
+            style: Style {
+                position_type: PositionType::Absolute,
+                position: UiRect {
+                    top: Val::Px(40.),
+                    left: Val::Px(10.),
+                    ..Default::default()
+                },
+                padding: UiRect::all(Val::Px(10.)),
+                ..Default::default()
+            },
+            background_color: Color::rgba(0., 0., 0., 0.8).into(),
+            z_index: ZIndex::Global(10),
+            ..Default::default()
+        })
+        .insert(Name::new(name))
+        .insert(marker)
+        .with_children(|parent| {
+            parent.spawn(TextBundle::from_section(
+                content,
+                TextStyle {
+                    font: loaded_assets.font.clone(),
+                    font_size: 14.,
+                    color: Color::WHITE,
+                },
+            ));
+        });
+}
@@ -0,0 +1,88 @@
+use crate::{
+    component::HintHighlight,
+    event::HintRequestEvent,
+    resource::{Board, GameStats, LoadedAssets},
+    solver::Hint,
+    system::mark::cover_size,
+};
+use bevy::{log, prelude::*};
+
+pub fn hint_request_handler(
+    mut commands: Commands,
+    board: Res<Board>,
+    mut stats: ResMut<GameStats>,
+    loaded_assets: Res<LoadedAssets>,
+    covers: Query<&Sprite>,
+    highlights: Query<Entity, With<HintHighlight>>,
+    mut hint_request_evr: EventReader<HintRequestEvent>,
+) {
+    if hint_request_evr.iter().count() == 0 || stats.is_finished() {
+        return;
+    }
+    // Only the latest hint is displayed
+    for entity in highlights.iter() {
+        commands.entity(entity).despawn_recursive();
+    }
+
+    let Some(hint) = Hint::find(&board.view()) else {
+        log::info!("No hint available");
+        return;
+    };
+    stats.hints_used += 1;
+    let (color, label) = match hint {
+        Hint::Safe(coords) => {
+            log::info!("Hint: tile {coords} is safe");
+            (Color::rgba(0., 1., 0., 0.4), None)
+        }
+        Hint::Mine(coords) => {
+            log::info!("Hint: tile {coords} is a mine");
+            (Color::rgba(1., 0., 0., 0.4), None)
+        }
+        Hint::Guess {
+            coordinates,
+            mine_probability,
+        } => {
+            let percent = (mine_probability * 100.).round();
+            log::info!(
+                "Hint: no deduction, tile {coordinates} is the safest guess ({percent}% risk)"
+            );
+            (Color::rgba(1., 1., 0., 0.4), Some(format!("{percent}%")))
+        }
+    };
+
+    let Some(entity) = board.tile_to_uncover(&hint.coordinates()).copied() else {
+        return;
+    };
+    let size = cover_size(&covers, entity, &board);
+    commands.entity(entity).with_children(|parent| {
+        parent
+            .spawn(SpriteBundle {
+                sprite: Sprite {
+                    color,
+                    custom_size: Some(Vec2::splat(size)),
+                    ..Default::default()
+                },
+                transform: Transform::from_xyz(0., 0., 2.),
+                ..Default::default()
+            })
+            .insert(Name::new("Hint"))
+            .insert(HintHighlight)
+            .with_children(|parent| {
+                if let Some(label) = label {
+                    parent.spawn(Text2dBundle {
+                        text: Text::from_section(
+                            label,
+                            TextStyle {
+                                font: loaded_assets.font.clone(),
+                                font_size: size / 3.,
+                                color: Color::BLACK,
+                            },
+                        )
+                        .with_alignment(TextAlignment::CENTER),
+                        transform: Transform::from_xyz(0., 0., 1.),
+                        ..Default::default()
+                    });
+                }
+            });
+    });
+}
@@ -0,0 +1,22 @@
+use crate::{
+    component::Hud,
+    resource::{EndlessWorld, GameStats},
+};
+use bevy::prelude::*;
+
+pub fn update_hud(
+    stats: Res<GameStats>,
+    endless_world: Option<Res<EndlessWorld>>,
+    mut texts: Query<&mut Text, With<Hud>>,
+) {
+    if !stats.is_changed() {
+        return;
+    }
+    let value = match endless_world {
+        Some(world) => format!("Score: {} (best: {})", world.score(), world.best_score()),
+        None => format!("Lives: {}", stats.lives_left),
+    };
+    for mut text in texts.iter_mut() {
+        text.sections[0].value = value.clone();
+    }
+}
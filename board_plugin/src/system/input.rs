@@ -1,46 +1,215 @@
-use crate::{event::TileTriggerEvent, Board};
+use crate::{
+    component::TileCursor,
+    event::{HintRequestEvent, TileChordEvent, TileMarkEvent, TileTriggerEvent},
+    resource::{GameStats, Heatmap, InputAction, InputBindings, TilePress},
+    Board,
+};
 use bevy::{
+    ecs::system::SystemParam,
     input::{mouse::MouseButtonInput, ButtonState},
     log,
     prelude::*,
 };
 
+/// Mouse play: tiles are pressed on button press and played on release. Dragging the cursor off
+/// the pressed tile cancels the press
+#[allow(clippy::too_many_arguments)]
 pub fn input_handling(
     windows: Res<Windows>,
+    cameras: Query<(&Camera, &GlobalTransform), With<Camera2d>>,
     board: Res<Board>,
+    stats: Res<GameStats>,
+    bindings: Res<InputBindings>,
+    mut press: ResMut<TilePress>,
     mut button_evr: EventReader<MouseButtonInput>,
     mut tile_trigger_ewr: EventWriter<TileTriggerEvent>,
+    mut tile_mark_ewr: EventWriter<TileMarkEvent>,
+    mut tile_chord_ewr: EventWriter<TileChordEvent>,
 ) {
     let window = windows.get_primary().expect("Failed to get primary window");
 
-    for event in button_evr.iter() {
-        let ButtonState::Released = event.state else {
-            continue;
-        };
-
-        if !matches!(event.button, MouseButton::Left | MouseButton::Right) {
-            continue;
+    // The board is frozen once the game is finished
+    if stats.is_finished() {
+        button_evr.clear();
+        if press.pressed.is_some() {
+            press.pressed = None;
         }
+        return;
+    }
 
-        let Some(pos) = window.cursor_position() else {
+    let hovered = window
+        .cursor_position()
+        .and_then(|pos| board.mouse_position(window, cameras.iter().next(), pos));
+
+    for event in button_evr.iter() {
+        let Some(action) = bindings.mouse_action(event.button) else {
             continue;
         };
 
-        log::trace!("Mouse button pressed: {:?} at {pos}", event.button);
-        let Some(coordinates) = board.mouse_position(window, pos) else {
+        let Some(coordinates) = hovered else {
             continue;
         };
+        log::trace!(
+            "Mouse button {:?}: {:?} on {coordinates}",
+            event.button,
+            event.state
+        );
+
+        match event.state {
+            ButtonState::Pressed => {
+                press.pressed = Some((action, coordinates));
+                continue;
+            }
+            ButtonState::Released if press.pressed == Some((action, coordinates)) => {
+                press.pressed = None;
+            }
+            // The press was cancelled, or started before the game
+            ButtonState::Released => continue,
+        }
 
-        match event.button {
-            MouseButton::Left => {
+        match action {
+            InputAction::Reveal => {
                 log::info!("Trying to uncover tile on {coordinates}");
                 tile_trigger_ewr.send(coordinates.into());
             }
-            MouseButton::Right => {
+            InputAction::Flag => {
                 log::info!("Trying to mark tile on {coordinates}");
-                // TODO: generate an event
+                tile_mark_ewr.send(coordinates.into());
+            }
+            InputAction::Chord => {
+                log::info!("Trying to chord tile on {coordinates}");
+                tile_chord_ewr.send(coordinates.into());
             }
             _ => (),
         }
     }
+
+    // Dragging off the pressed tile cancels the press
+    if let Some((action, coordinates)) = press.pressed {
+        if hovered != Some(coordinates) {
+            log::debug!("Cancelled {action:?} press on {coordinates}");
+            press.pressed = None;
+        }
+    }
+}
+
+/// Tile cursor input of a frame
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub(crate) struct CursorCommand {
+    /// Cursor movement
+    pub delta: (i32, i32),
+    pub reveal: bool,
+    pub flag: bool,
+    pub chord: bool,
+}
+
+/// Moves the tile cursor and plays on the selected tile
+#[derive(SystemParam)]
+pub(crate) struct CursorControl<'w, 's> {
+    board: Res<'w, Board>,
+    stats: Res<'w, GameStats>,
+    cursors: Query<
+        'w,
+        's,
+        (
+            &'static mut TileCursor,
+            &'static mut Transform,
+            &'static mut Visibility,
+        ),
+    >,
+    tile_trigger_ewr: EventWriter<'w, 's, TileTriggerEvent>,
+    tile_mark_ewr: EventWriter<'w, 's, TileMarkEvent>,
+    tile_chord_ewr: EventWriter<'w, 's, TileChordEvent>,
+}
+
+impl CursorCommand {
+    fn is_empty(&self) -> bool {
+        *self == Self::default()
+    }
+}
+
+impl CursorControl<'_, '_> {
+    pub fn apply(&mut self, command: CursorCommand) {
+        if command.is_empty() {
+            return;
+        }
+        let size = (!self.board.unbounded)
+            .then(|| (self.board.tile_map.width(), self.board.tile_map.height()));
+        for (mut cursor, mut transform, mut visibility) in self.cursors.iter_mut() {
+            // The first input only reveals the cursor
+            if !visibility.is_visible {
+                visibility.is_visible = true;
+                continue;
+            }
+            cursor.shift(command.delta, size);
+            transform.translation = cursor.translation(self.board.tile_size);
+
+            if self.stats.is_finished() {
+                continue;
+            }
+            let coordinates = cursor.coordinates;
+            if command.reveal {
+                log::info!("Trying to uncover tile on {coordinates}");
+                self.tile_trigger_ewr.send(coordinates.into());
+            }
+            if command.flag {
+                log::info!("Trying to mark tile on {coordinates}");
+                self.tile_mark_ewr.send(coordinates.into());
+            }
+            if command.chord {
+                log::info!("Trying to chord tile on {coordinates}");
+                self.tile_chord_ewr.send(coordinates.into());
+            }
+        }
+    }
+}
+
+/// Keyboard play: the arrows, WASD or vim keys move the tile cursor, clamped to the board edges.
+/// The reveal, flag and chord bound keys play on the selected tile
+pub fn cursor_input_handling(
+    keys: Res<Input<KeyCode>>,
+    bindings: Res<InputBindings>,
+    mut cursor_control: CursorControl,
+) {
+    let pressed = |codes: [KeyCode; 3]| keys.any_just_pressed(codes);
+    let [left, right, up, down] = InputBindings::CURSOR_KEYS;
+    let mut delta = (0, 0);
+    if pressed(left) {
+        delta.0 -= 1;
+    }
+    if pressed(right) {
+        delta.0 += 1;
+    }
+    if pressed(up) {
+        delta.1 += 1;
+    }
+    if pressed(down) {
+        delta.1 -= 1;
+    }
+    cursor_control.apply(CursorCommand {
+        delta,
+        reveal: bindings.get(InputAction::Reveal).key_just_pressed(&keys),
+        flag: bindings.get(InputAction::Flag).key_just_pressed(&keys),
+        chord: bindings.get(InputAction::Chord).key_just_pressed(&keys),
+    });
+}
+
+pub fn hint_input_handling(
+    keys: Res<Input<KeyCode>>,
+    gamepads: Res<Gamepads>,
+    gamepad_buttons: Res<Input<GamepadButton>>,
+    bindings: Res<InputBindings>,
+    mut hint_request_ewr: EventWriter<HintRequestEvent>,
+) {
+    if bindings.just_pressed(InputAction::Hint, &keys, &gamepads, &gamepad_buttons) {
+        log::info!("Hint requested");
+        hint_request_ewr.send(HintRequestEvent);
+    }
+}
+
+pub fn heatmap_input_handling(keys: Res<Input<KeyCode>>, mut heatmap: ResMut<Heatmap>) {
+    if keys.just_pressed(InputBindings::HEATMAP_KEY) {
+        heatmap.enabled = !heatmap.enabled;
+        log::info!("Heatmap overlay enabled: {}", heatmap.enabled);
+    }
 }
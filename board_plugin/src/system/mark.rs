@@ -0,0 +1,112 @@
+use crate::{
+    event::TileMarkEvent,
+    resource::{Board, BoardTheme, GameStats, LoadedAssets, TileAtlas},
+    stack_count_text_bundle,
+};
+use bevy::{ecs::query::ReadOnlyWorldQuery, log, prelude::*};
+
+#[allow(clippy::too_many_arguments)]
+pub fn mark_tiles(
+    mut commands: Commands,
+    mut board: ResMut<Board>,
+    mut stats: ResMut<GameStats>,
+    loaded_assets: Res<LoadedAssets>,
+    theme: Res<BoardTheme>,
+    covers: Query<&Sprite>,
+    mut atlas_covers: Query<&mut TextureAtlasSprite>,
+    mut tile_mark_evr: EventReader<TileMarkEvent>,
+) {
+    for TileMarkEvent(coords) in tile_mark_evr.iter() {
+        stats.right_clicks += 1;
+        let Some((entity, flags)) = board.try_toggle_mark(coords) else {
+            continue;
+        };
+        stats.effective_clicks += 1;
+        match flags {
+            0 => log::info!("Unmarked tile {coords}"),
+            _ => log::info!("Marked tile {coords} with {flags} flag(s)"),
+        }
+        let size = cover_size(&covers, entity, &board);
+        spawn_flags(
+            &mut commands,
+            entity,
+            flags,
+            size,
+            &loaded_assets,
+            &theme,
+            atlas_covers.get_mut(entity).ok(),
+        );
+    }
+}
+
+/// Retrieves the sprite size of a tile cover
+pub(crate) fn cover_size<F: ReadOnlyWorldQuery>(
+    covers: &Query<&Sprite, F>,
+    entity: Entity,
+    board: &Board,
+) -> f32 {
+    covers
+        .get(entity)
+        .ok()
+        .and_then(|sprite| sprite.custom_size)
+        .map_or(board.tile_size, |size| size.x)
+}
+
+/// Replaces the flags displayed on the tile cover `entity`. Texture atlas covers display the
+/// flag themselves, through their `atlas_cover` sprite
+pub(crate) fn spawn_flags(
+    commands: &mut Commands,
+    entity: Entity,
+    flags: u8,
+    size: f32,
+    loaded_assets: &LoadedAssets,
+    theme: &BoardTheme,
+    atlas_cover: Option<Mut<TextureAtlasSprite>>,
+) {
+    // We replace the previous flag, if any
+    commands.entity(entity).despawn_descendants();
+    if let Some(mut sprite) = atlas_cover {
+        sprite.index = match flags {
+            0 => TileAtlas::COVER,
+            _ => TileAtlas::FLAG,
+        };
+        if flags > 1 {
+            commands.entity(entity).with_children(|parent| {
+                parent.spawn(stack_count_text_bundle(
+                    flags,
+                    loaded_assets.font.clone(),
+                    theme.stack_count_color,
+                    size,
+                ));
+            });
+        }
+        return;
+    }
+    if flags == 0 {
+        return;
+    }
+    commands.entity(entity).with_children(|parent| {
+        parent
+            .spawn(SpriteBundle {
+                sprite: Sprite {
+                    custom_size: Some(Vec2::splat(size)),
+                    ..Default::default()
+                },
+                texture: loaded_assets.flag_image.clone(),
+                transform: Transform::from_xyz(0., 0., 1.),
+                ..Default::default()
+            })
+            .insert(Name::new("Flag"))
+            .with_children(|parent| {
+                // Multiple flags display their count
+                if flags > 1 {
+                    parent.spawn(stack_count_text_bundle(
+                        flags,
+                        loaded_assets.font.clone(),
+                        theme.stack_count_color,
+                        size,
+                    ));
+                }
+            });
+    });
+}
@@ -1,2 +1,18 @@
+pub mod bot;
+pub mod chunk;
+pub mod daily;
+pub mod endless;
+pub mod game_over;
+pub mod gamepad;
+pub mod heatmap;
+pub mod high_scores;
+pub mod hint;
+pub mod hud;
 pub mod input;
+pub mod mark;
+pub mod player_stats;
+pub mod press;
+pub mod stats;
+pub mod theme;
+pub mod touch;
 pub mod uncover;
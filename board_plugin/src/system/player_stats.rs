@@ -0,0 +1,92 @@
+use crate::{
+    component::StatsScreen,
+    event::{BoardCompletedEvent, GameOverEvent},
+    resource::{
+        BoardOptions, BotPlayer, DailyChallenge, EndlessWorld, InputBindings, LoadedAssets,
+        PlayerStats,
+    },
+    system::high_scores::spawn_text_panel,
+};
+use bevy::{log, prelude::*};
+
+/// Accounts for finished games in the lifetime player statistics. The endless world keeps its
+/// own best score and daily challenges their own results, games played by a bot are not recorded
+pub fn record_player_stats(
+    board_options: Option<Res<BoardOptions>>,
+    endless_world: Option<Res<EndlessWorld>>,
+    daily: Option<Res<DailyChallenge>>,
+    bot_player: Option<Res<BotPlayer>>,
+    mut player_stats: ResMut<PlayerStats>,
+    mut board_completed_evr: EventReader<BoardCompletedEvent>,
+    mut game_over_evr: EventReader<GameOverEvent>,
+) {
+    if endless_world.is_some() || daily.is_some() || bot_player.is_some() {
+        board_completed_evr.clear();
+        game_over_evr.clear();
+        return;
+    }
+    let finished: Vec<_> = board_completed_evr
+        .iter()
+        .map(|BoardCompletedEvent(stats)| stats)
+        .chain(game_over_evr.iter().map(|GameOverEvent(stats)| stats))
+        .collect();
+    if finished.is_empty() {
+        return;
+    }
+    let board_options = board_options.map(|o| o.clone()).unwrap_or_default();
+    let key = board_options.difficulty_key();
+    let label = board_options.difficulty_label();
+    for stats in finished {
+        player_stats.record(&key, &label, stats);
+    }
+    if let Err(e) = player_stats.save() {
+        log::error!("Failed to save player statistics: {e}");
+    }
+}
+
+/// Toggles the lifetime statistics screen of the current difficulty with F2
+pub fn stats_screen_input_handling(
+    mut commands: Commands,
+    keys: Res<Input<KeyCode>>,
+    board_options: Option<Res<BoardOptions>>,
+    player_stats: Res<PlayerStats>,
+    loaded_assets: Res<LoadedAssets>,
+    screens: Query<Entity, With<StatsScreen>>,
+) {
+    if !keys.just_pressed(InputBindings::STATS_KEY) {
+        return;
+    }
+    if !screens.is_empty() {
+        for entity in screens.iter() {
+            commands.entity(entity).despawn_recursive();
+        }
+        return;
+    }
+    let board_options = board_options.map(|o| o.clone()).unwrap_or_default();
+    let mut content = format!("Statistics - {}\n\n", board_options.difficulty_label());
+    match player_stats.get(&board_options.difficulty_key()) {
+        Some(stats) => {
+            content += &format!(
+                "Played: {}\nWon: {} ({:.0}%)\nLost: {}\nStreak: {} (best {})\n",
+                stats.games_played,
+                stats.games_won,
+                stats.win_rate() * 100.,
+                stats.games_lost,
+                stats.current_streak,
+                stats.best_streak,
+            );
+            if let Some(time) = stats.average_win_time() {
+                content += &format!("Average time: {:.2}s\n", time.as_secs_f32());
+            }
+            content += &format!("Tiles uncovered: {}\n", stats.tiles_uncovered);
+        }
+        None => content += "No game played yet\n",
+    }
+    spawn_text_panel(
+        &mut commands,
+        StatsScreen,
+        "Statistics",
+        content,
+        &loaded_assets,
+    );
+}
@@ -0,0 +1,81 @@
+use crate::{
+    component::PressedTile,
+    resource::{Board, InputAction, TileAtlas, TilePress},
+};
+use bevy::prelude::*;
+
+/// Brightness added to the depressed tile covers
+const PRESS_HIGHLIGHT: f32 = 0.15;
+
+/// Depresses the covers under the held mouse button: the pressed tile when revealing, and its
+/// whole chord area when chording. Covers are restored once released, cancelled or uncovered.
+/// Texture atlas covers are depressed by displaying an empty uncovered tile
+pub fn update_pressed_tiles(
+    mut commands: Commands,
+    board: Res<Board>,
+    press: Res<TilePress>,
+    mut covers: Query<&mut Sprite>,
+    mut atlas_covers: Query<&mut TextureAtlasSprite>,
+    pressed: Query<(Entity, &PressedTile)>,
+) {
+    if !press.is_changed() && !board.is_changed() {
+        return;
+    }
+    let targets: Vec<Entity> = match press.pressed {
+        Some((InputAction::Reveal, coords)) => board
+            .tile_to_uncover(&coords)
+            .copied()
+            .into_iter()
+            .collect(),
+        Some((InputAction::Chord, coords)) => board
+            .tile_to_uncover(&coords)
+            .copied()
+            .into_iter()
+            .chain(board.adjacent_covered_tiles(coords))
+            .collect(),
+        _ => Vec::new(),
+    };
+
+    for (entity, tile) in pressed.iter() {
+        if targets.contains(&entity) {
+            continue;
+        }
+        if let Ok(mut sprite) = covers.get_mut(entity) {
+            sprite.color = tile.color;
+        }
+        // Covers flagged while pressed keep their flag
+        if let Ok(mut sprite) = atlas_covers.get_mut(entity) {
+            if sprite.index == TileAtlas::REVEALED {
+                sprite.index = TileAtlas::COVER;
+            }
+        }
+        commands.entity(entity).remove::<PressedTile>();
+    }
+    for entity in targets {
+        if pressed.contains(entity) {
+            continue;
+        }
+        if let Ok(mut sprite) = atlas_covers.get_mut(entity) {
+            commands.entity(entity).insert(PressedTile {
+                color: sprite.color,
+            });
+            sprite.index = TileAtlas::REVEALED;
+        } else if let Ok(mut sprite) = covers.get_mut(entity) {
+            commands.entity(entity).insert(PressedTile {
+                color: sprite.color,
+            });
+            sprite.color = pressed_color(sprite.color);
+        }
+    }
+}
+
+/// Depressed color of a tile cover
+pub(crate) fn pressed_color(color: Color) -> Color {
+    let [r, g, b, a] = color.as_rgba_f32();
+    Color::rgba(
+        r + PRESS_HIGHLIGHT,
+        g + PRESS_HIGHLIGHT,
+        b + PRESS_HIGHLIGHT,
+        a,
+    )
+}
@@ -0,0 +1,13 @@
+use crate::resource::GameStats;
+use bevy::prelude::*;
+
+/// Measures the play time, from the first click until the game is finished.
+///
+/// The play time is not displayed while playing, so ticking it doesn't flag the stats as
+/// changed, which would update the HUD every frame
+pub fn tick_game_timer(time: Res<Time>, mut stats: ResMut<GameStats>) {
+    if stats.clicks() == 0 || stats.is_finished() {
+        return;
+    }
+    stats.bypass_change_detection().elapsed += time.delta();
+}
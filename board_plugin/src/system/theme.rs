@@ -0,0 +1,114 @@
+use crate::{
+    component::{BoardBackground, BombNeighbor, Coordinates, PressedTile, StackCount},
+    resource::{Board, BoardTheme, InputBindings, LoadedAssets, TileAtlas},
+    system::press::pressed_color,
+};
+use bevy::{log, prelude::*};
+
+/// Switches to the next built-in theme
+pub fn theme_input_handling(keys: Res<Input<KeyCode>>, mut theme: ResMut<BoardTheme>) {
+    if keys.just_pressed(InputBindings::THEME_KEY) {
+        *theme = theme.next_built_in();
+        log::info!("Switched to the {} theme", theme.name);
+    }
+}
+
+/// Restyles the spawned board when the theme changes, the colors, font and sprites of the
+/// previously applied theme are replaced. The texture atlas, if any, is redrawn
+#[allow(clippy::too_many_arguments)]
+pub fn apply_theme(
+    mut applied: Local<Option<BoardTheme>>,
+    theme: Res<BoardTheme>,
+    board: Res<Board>,
+    asset_server: Res<AssetServer>,
+    mut loaded_assets: ResMut<LoadedAssets>,
+    atlas: Option<Res<TileAtlas>>,
+    mut image_assets: ResMut<Assets<Image>>,
+    mut sprites: Query<&mut Sprite>,
+    tiles: Query<Entity, With<Coordinates>>,
+    backgrounds: Query<Entity, With<BoardBackground>>,
+    mut pressed: Query<&mut PressedTile>,
+    numbers: Query<(&BombNeighbor, &Children)>,
+    stack_counts: Query<Entity, With<StackCount>>,
+    mut texts: Query<&mut Text>,
+    mut images: Query<&mut Handle<Image>>,
+) {
+    if !theme.is_changed() {
+        return;
+    }
+    // The board is spawned with the current theme
+    let Some(previous) = applied.replace(theme.clone()) else {
+        return;
+    };
+    if previous == *theme {
+        return;
+    }
+
+    for entity in backgrounds.iter() {
+        if let Ok(mut sprite) = sprites.get_mut(entity) {
+            sprite.color = theme.background_color;
+        }
+    }
+    for entity in tiles.iter() {
+        if let Ok(mut sprite) = sprites.get_mut(entity) {
+            sprite.color = match sprite.color == previous.detonated_color {
+                true => theme.detonated_color,
+                false => theme.revealed_color,
+            };
+        }
+    }
+    for entity in board.covered_tiles.values() {
+        let Ok(mut sprite) = sprites.get_mut(*entity) else {
+            continue;
+        };
+        sprite.color = match pressed.get_mut(*entity) {
+            Ok(mut tile) => {
+                tile.color = theme.cover_color;
+                pressed_color(theme.cover_color)
+            }
+            Err(_) => theme.cover_color,
+        };
+    }
+
+    if let Some(atlas) = atlas {
+        atlas.redraw(&theme, &mut image_assets);
+    }
+
+    let set_color = |text: &mut Text, color| {
+        for section in text.sections.iter_mut() {
+            section.style.color = color;
+        }
+    };
+    for (neighbor, children) in numbers.iter() {
+        for child in children.iter() {
+            if let Ok(mut text) = texts.get_mut(*child) {
+                set_color(&mut text, theme.number_color(neighbor.count));
+            }
+        }
+    }
+    for entity in stack_counts.iter() {
+        if let Ok(mut text) = texts.get_mut(entity) {
+            set_color(&mut text, theme.stack_count_color);
+        }
+    }
+
+    // Font and sprite handles of the previous theme are swapped in place
+    let assets = LoadedAssets::load(&theme, &asset_server);
+    if assets != *loaded_assets {
+        for mut text in texts.iter_mut() {
+            for section in text.sections.iter_mut() {
+                if section.style.font == loaded_assets.font {
+                    section.style.font = assets.font.clone();
+                }
+            }
+        }
+        for mut image in images.iter_mut() {
+            if *image == loaded_assets.bomb_image {
+                *image = assets.bomb_image.clone();
+            } else if *image == loaded_assets.flag_image {
+                *image = assets.flag_image.clone();
+            }
+        }
+        *loaded_assets = assets;
+    }
+}
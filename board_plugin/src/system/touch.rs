@@ -0,0 +1,134 @@
+use crate::{
+    event::{TileMarkEvent, TileTriggerEvent},
+    resource::{Board, GameStats},
+};
+use bevy::{
+    input::touch::Touch,
+    log,
+    prelude::*,
+    utils::{HashMap, HashSet},
+};
+
+/// Hold duration turning a press into a long press, in seconds
+const LONG_PRESS_DURATION: f32 = 0.5;
+/// Finger movement, in logical pixels, beyond which a press is no longer a tap
+const TAP_SLOP: f32 = 10.;
+/// Camera zoom bounds
+const MIN_ZOOM: f32 = 0.2;
+const MAX_ZOOM: f32 = 4.;
+
+/// Ongoing touches
+#[derive(Debug, Default)]
+pub struct TouchState {
+    /// Press time of the touches
+    started: HashMap<u64, f32>,
+    /// Touches which already flagged a tile
+    long_pressed: HashSet<u64>,
+    /// Was a two finger gesture done since every finger was lifted
+    gesture: bool,
+}
+
+/// Touch play: a tap reveals and a long press flags the touched tile, two fingers pinch to
+/// zoom and drag to pan the camera
+#[allow(clippy::too_many_arguments)]
+pub fn touch_input_handling(
+    time: Res<Time>,
+    windows: Res<Windows>,
+    touches: Res<Touches>,
+    board: Res<Board>,
+    stats: Res<GameStats>,
+    mut state: Local<TouchState>,
+    mut cameras: Query<
+        (
+            &Camera,
+            &GlobalTransform,
+            &mut Transform,
+            &mut OrthographicProjection,
+        ),
+        With<Camera2d>,
+    >,
+    mut tile_trigger_ewr: EventWriter<TileTriggerEvent>,
+    mut tile_mark_ewr: EventWriter<TileMarkEvent>,
+) {
+    let window = windows.get_primary().expect("Failed to get primary window");
+    let now = time.elapsed_seconds();
+    for touch in touches.iter_just_pressed() {
+        state.started.insert(touch.id(), now);
+    }
+
+    let pressed: Vec<&Touch> = touches.iter().collect();
+    let mut camera = cameras.iter_mut().next();
+    if let ([first, second], Some((_, _, transform, projection))) = (&pressed[..], &mut camera) {
+        state.gesture = true;
+        // Pinch zoom, from the distance variation between the two fingers
+        let previous = first
+            .previous_position()
+            .distance(second.previous_position());
+        let current = first.position().distance(second.position());
+        if previous > 0. && current > 0. {
+            projection.scale = (projection.scale * previous / current).clamp(MIN_ZOOM, MAX_ZOOM);
+        }
+        // Pan, following the middle of the fingers. Touch positions have a top left origin
+        let delta = (first.delta() + second.delta()) / 2.;
+        transform.translation.x -= delta.x * projection.scale;
+        transform.translation.y += delta.y * projection.scale;
+    }
+
+    // Touch positions have a top left origin, unlike the cursor position
+    let picking_camera = camera.as_ref().map(|(c, t, _, _)| (*c, *t));
+    let tile_at = |touch: &Touch| {
+        let position = Vec2::new(touch.position().x, window.height() - touch.position().y);
+        board.mouse_position(window, picking_camera, position)
+    };
+    let is_still = |touch: &Touch| touch.distance().length() < TAP_SLOP;
+
+    if !state.gesture && !stats.is_finished() {
+        // Long presses flag the tile while the finger is still held
+        for touch in pressed.iter().filter(|t| is_still(t)) {
+            let held = state
+                .started
+                .get(&touch.id())
+                .map_or(0., |start| now - start);
+            if held < LONG_PRESS_DURATION || state.long_pressed.contains(&touch.id()) {
+                continue;
+            }
+            state.long_pressed.insert(touch.id());
+            if let Some(coordinates) = tile_at(touch) {
+                log::info!("Trying to mark tile on {coordinates}");
+                tile_mark_ewr.send(coordinates.into());
+            }
+        }
+        // Taps reveal the tile
+        for touch in touches.iter_just_released().filter(|t| is_still(t)) {
+            if state.long_pressed.contains(&touch.id()) {
+                continue;
+            }
+            if let Some(coordinates) = tile_at(touch) {
+                log::info!("Trying to uncover tile on {coordinates}");
+                tile_trigger_ewr.send(coordinates.into());
+            }
+        }
+    }
+
+    for touch in touches
+        .iter_just_released()
+        .chain(touches.iter_just_cancelled())
+    {
+        state.started.remove(&touch.id());
+        state.long_pressed.remove(&touch.id());
+    }
+    if pressed.is_empty() {
+        state.gesture = false;
+    }
+}
+
+/// Resets the camera zoom and pan of the previous board
+pub fn reset_camera(
+    mut cameras: Query<(&mut Transform, &mut OrthographicProjection), With<Camera2d>>,
+) {
+    for (mut transform, mut projection) in cameras.iter_mut() {
+        transform.translation.x = 0.;
+        transform.translation.y = 0.;
+        projection.scale = 1.;
+    }
+}
@@ -1,50 +1,116 @@
 use crate::{
     component::{Bomb, BombNeighbor, Coordinates, Uncover},
-    event::TileTriggerEvent,
-    resource::Board,
+    event::{
+        BoardCompletedEvent, BombExplosionEvent, GameOverEvent, TileChordEvent, TileTriggerEvent,
+    },
+    resource::{Board, BoardTheme, GameOutcome, GameStats, LoadedAssets},
+    system::mark::{cover_size, spawn_flags},
 };
 use bevy::{log, prelude::*};
 
 pub fn trigger_event_handler(
     mut commands: Commands,
     board: Res<Board>,
+    mut stats: ResMut<GameStats>,
     mut tile_trigger_evr: EventReader<TileTriggerEvent>,
 ) {
-    tile_trigger_evr
-        .iter()
-        .filter_map(|TileTriggerEvent(coords)| board.tile_to_uncover(coords))
-        .for_each(|entity| {
+    for TileTriggerEvent(coords) in tile_trigger_evr.iter() {
+        stats.left_clicks += 1;
+        if let Some(entity) = board.tile_to_uncover(coords) {
+            stats.effective_clicks += 1;
             commands.entity(*entity).insert(Uncover);
-        })
+        }
+    }
 }
 
+pub fn chord_event_handler(
+    mut commands: Commands,
+    board: Res<Board>,
+    mut stats: ResMut<GameStats>,
+    mut tile_chord_evr: EventReader<TileChordEvent>,
+) {
+    for TileChordEvent(coords) in tile_chord_evr.iter() {
+        stats.chord_clicks += 1;
+        let Some(targets) = board.chord_targets(coords).filter(|t| !t.is_empty()) else {
+            continue;
+        };
+        stats.effective_clicks += 1;
+        for entity in targets {
+            commands.entity(entity).insert(Uncover);
+        }
+    }
+}
+
+#[allow(clippy::too_many_arguments)]
 pub fn uncover_tiles(
     mut commands: Commands,
     mut board: ResMut<Board>,
+    mut stats: ResMut<GameStats>,
+    loaded_assets: Res<LoadedAssets>,
+    theme: Res<BoardTheme>,
     children: Query<(Entity, &Parent), With<Uncover>>,
     parents: Query<(&Coordinates, Option<&Bomb>, Option<&BombNeighbor>)>,
+    covers: Query<&Sprite>,
+    mut atlas_covers: Query<&mut TextureAtlasSprite>,
+    mut bomb_explosion_ewr: EventWriter<BombExplosionEvent>,
+    mut board_completed_ewr: EventWriter<BoardCompletedEvent>,
+    mut game_over_ewr: EventWriter<GameOverEvent>,
 ) {
     // We iterate through tile covers to uncover
     for (entity, parent) in children.iter() {
-        // we destroy the tile cover entity
-        commands.entity(entity).despawn_recursive();
-
         let (coords, bomb, bomb_counter) = match parents.get(parent.get()) {
             Ok(v) => v,
             Err(e) => {
                 log::error!("{e}");
+                commands.entity(entity).despawn_recursive();
                 continue;
             }
         };
 
+        if let Some(bomb) = bomb {
+            log::info!("Boom!");
+            stats.mines_hit += 1;
+            stats.lives_left = stats.lives_left.saturating_sub(1);
+            bomb_explosion_ewr.send(BombExplosionEvent {
+                coordinates: *coords,
+                lives_left: stats.lives_left,
+            });
+            // While the player has lives left, the mine is flagged instead of uncovered
+            if stats.lives_left > 0 {
+                commands.entity(entity).remove::<Uncover>();
+                if board.try_mark_exploded(coords, bomb.count).is_some() {
+                    let size = cover_size(&covers, entity, &board);
+                    spawn_flags(
+                        &mut commands,
+                        entity,
+                        bomb.count,
+                        size,
+                        &loaded_assets,
+                        &theme,
+                        atlas_covers.get_mut(entity).ok(),
+                    );
+                }
+                continue;
+            }
+        }
+
+        // we destroy the tile cover entity
+        commands.entity(entity).despawn_recursive();
+
         // We remove the entity from the board covered tile map
         match board.try_uncover_tile(coords) {
-            Some(e) => log::debug!("Uncovered tile {coords} (entity: {e:?})"),
+            Some(e) => {
+                log::debug!("Uncovered tile {coords} (entity: {e:?})");
+                stats.tiles_uncovered += bomb.is_none() as u32;
+            }
             None => log::debug!("Tried to uncover an already uncovered tile"),
         }
         if bomb.is_some() {
-            log::info!("Boom!");
-            // TODO: Add explosion event
+            if !stats.is_finished() {
+                log::info!("Game over");
+                stats.outcome = Some(GameOutcome::Defeat);
+                game_over_ewr.send(GameOverEvent(stats.clone()));
+            }
         }
         // If the tile is empty..
         else if bomb_counter.is_none() {
@@ -58,4 +124,17 @@ pub fn uncover_tiles(
                 });
         }
     }
+
+    if !children.is_empty() && !stats.is_finished() && board.is_completed() {
+        log::info!(
+            "Board completed in {:.2}s! 3BV: {}, 3BV/s: {:.2}, IOE: {:.2}, correctness: {:.0}%",
+            stats.elapsed.as_secs_f32(),
+            stats.bbbv,
+            stats.bbbv_per_second(),
+            stats.ioe(),
+            stats.correctness() * 100.
+        );
+        stats.outcome = Some(GameOutcome::Victory);
+        board_completed_ewr.send(BoardCompletedEvent(stats.clone()));
+    }
 }
@@ -0,0 +1,299 @@
+use bevy::prelude::*;
+
+use board_plugin::resource::{BoardOptions, TileSize};
+
+use crate::{
+    menu::{MenuAction, NORMAL_BUTTON},
+    AppState,
+};
+
+/// Custom game dialog, editing the board options with live validation
+pub struct CustomGamePlugin;
+
+/// Board options edited by the custom game dialog
+#[derive(Debug, Clone, Resource)]
+pub(crate) struct CustomGameForm(pub BoardOptions);
+
+/// Custom game dialog root marker component
+#[derive(Debug, Clone, Copy, Component)]
+struct CustomGameRoot;
+
+/// Editable field of the custom game dialog
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Component)]
+enum FormField {
+    Width,
+    Height,
+    Mines,
+    Padding,
+    SafeStart,
+    TileSize,
+}
+
+/// Button editing a field, numeric fields are stepped by `delta` and toggles are flipped
+#[derive(Debug, Clone, Copy, Component)]
+struct FormStep {
+    field: FormField,
+    delta: i32,
+}
+
+/// Displayed value of a field
+#[derive(Debug, Clone, Copy, Component)]
+struct FormValue(FormField);
+
+/// Validation messages text marker component
+#[derive(Debug, Clone, Copy, Component)]
+struct FormErrors;
+
+impl Plugin for CustomGamePlugin {
+    fn build(&self, app: &mut App) {
+        app.add_system_set(
+            SystemSet::on_enter(AppState::CustomGame).with_system(spawn_custom_game_dialog),
+        )
+        .add_system_set(
+            SystemSet::on_update(AppState::CustomGame)
+                .with_system(form_step_handling)
+                .with_system(update_form.after(form_step_handling)),
+        )
+        .add_system_set(
+            SystemSet::on_exit(AppState::CustomGame).with_system(despawn_custom_game_dialog),
+        );
+    }
+}
+
+impl FormField {
+    fn label(self) -> &'static str {
+        match self {
+            Self::Width => "Width",
+            Self::Height => "Height",
+            Self::Mines => "Mines",
+            Self::Padding => "Padding",
+            Self::SafeStart => "Safe start",
+            Self::TileSize => "Tile size",
+        }
+    }
+
+    /// Is the field a toggle rather than a number
+    fn is_toggle(self) -> bool {
+        matches!(self, Self::SafeStart | Self::TileSize)
+    }
+
+    fn value(self, options: &BoardOptions) -> String {
+        match self {
+            Self::Width => options.map_size.0.to_string(),
+            Self::Height => options.map_size.1.to_string(),
+            Self::Mines => options.bomb_count.to_string(),
+            Self::Padding => format!("{:.0}", options.tile_padding),
+            Self::SafeStart => if options.safe_start { "On" } else { "Off" }.to_string(),
+            Self::TileSize => match options.tile_size {
+                TileSize::Fixed(size) => format!("Fixed {size:.0}"),
+                TileSize::Adaptive { .. } => "Adaptive".to_string(),
+            },
+        }
+    }
+
+    fn apply(self, options: &mut BoardOptions, delta: i32) {
+        let step = |value: u16| (value as i32 + delta).clamp(0, 999) as u16;
+        match self {
+            Self::Width => options.map_size.0 = step(options.map_size.0),
+            Self::Height => options.map_size.1 = step(options.map_size.1),
+            Self::Mines => options.bomb_count = step(options.bomb_count),
+            Self::Padding => options.tile_padding = (options.tile_padding + delta as f32).max(0.),
+            Self::SafeStart => options.safe_start = !options.safe_start,
+            Self::TileSize => {
+                options.tile_size = match options.tile_size {
+                    TileSize::Fixed(_) => TileSize::default(),
+                    TileSize::Adaptive { .. } => TileSize::Fixed(30.),
+                }
+            }
+        }
+    }
+}
+
+fn spawn_custom_game_dialog(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    board_options: Option<Res<BoardOptions>>,
+) {
+    // The dialog starts from the current options, custom shapes and seeds can't be edited
+    let mut options = board_options.map(|o| o.clone()).unwrap_or_default();
+    options.map_size = options.dimensions();
+    options.mask = None;
+    options.seed = None;
+
+    let font: Handle<Font> = asset_server.load("fonts/pixeled.ttf");
+    let text_style = |font_size, color| TextStyle {
+        font: font.clone(),
+        font_size,
+        color,
+    };
+    let button = |width| ButtonBundle {
+        style: Style {
+            size: Size::new(Val::Px(width), Val::Px(40.)),
+            margin: UiRect::all(Val::Px(4.)),
+            justify_content: JustifyContent::Center,
+            align_items: AlignItems::Center,
+            ..Default::default()
+        },
+        background_color: NORMAL_BUTTON.into(),
+        ..Default::default()
+    };
+    let fields = [
+        FormField::Width,
+        FormField::Height,
+        FormField::Mines,
+        FormField::Padding,
+        FormField::SafeStart,
+        FormField::TileSize,
+    ];
+
+    commands
+        .spawn(NodeBundle {
+            style: Style {
+                position_type: PositionType::Absolute,
+                size: Size::new(Val::Percent(100.), Val::Percent(100.)),
+                flex_direction: FlexDirection::Column,
+                justify_content: JustifyContent::Center,
+                align_items: AlignItems::Center,
+                ..Default::default()
+            },
+            background_color: Color::rgba(0., 0., 0., 0.7).into(),
+            z_index: ZIndex::Global(20),
+            ..Default::default()
+        })
+        .insert(Name::new("Custom game"))
+        .insert(CustomGameRoot)
+        .with_children(|parent| {
+            parent.spawn(
+                TextBundle::from_section("Custom game", text_style(40., Color::WHITE)).with_style(
+                    Style {
+                        margin: UiRect::all(Val::Px(20.)),
+                        ..Default::default()
+                    },
+                ),
+            );
+            for field in fields {
+                parent
+                    .spawn(NodeBundle {
+                        style: Style {
+                            align_items: AlignItems::Center,
+                            ..Default::default()
+                        },
+                        ..Default::default()
+                    })
+                    .with_children(|row| {
+                        row.spawn(
+                            TextBundle::from_section(field.label(), text_style(18., Color::WHITE))
+                                .with_style(Style {
+                                    size: Size::new(Val::Px(200.), Val::Auto),
+                                    ..Default::default()
+                                }),
+                        );
+                        let spawn_step = |row: &mut ChildBuilder, delta: i32, label: &str| {
+                            row.spawn((button(40.), FormStep { field, delta }))
+                                .with_children(|b| {
+                                    b.spawn(TextBundle::from_section(
+                                        label,
+                                        text_style(18., Color::WHITE),
+                                    ));
+                                });
+                        };
+                        if !field.is_toggle() {
+                            spawn_step(row, -1, "-");
+                        }
+                        // Toggles are flipped by clicking their value
+                        let mut value = match field.is_toggle() {
+                            true => row.spawn((button(160.), FormStep { field, delta: 0 })),
+                            false => row.spawn(NodeBundle {
+                                style: button(160.).style,
+                                ..Default::default()
+                            }),
+                        };
+                        value.with_children(|b| {
+                            b.spawn(TextBundle::from_section(
+                                field.value(&options),
+                                text_style(18., Color::WHITE),
+                            ))
+                            .insert(FormValue(field));
+                        });
+                        if !field.is_toggle() {
+                            spawn_step(row, 1, "+");
+                        }
+                    });
+            }
+            parent
+                .spawn(
+                    TextBundle::from_section("", text_style(14., Color::RED)).with_style(Style {
+                        margin: UiRect::all(Val::Px(10.)),
+                        ..Default::default()
+                    }),
+                )
+                .insert(FormErrors);
+            for (label, action) in [
+                ("Start", MenuAction::StartCustom),
+                ("Back", MenuAction::Play),
+            ] {
+                parent
+                    .spawn(button(300.))
+                    .insert(action)
+                    .with_children(|b| {
+                        b.spawn(TextBundle::from_section(
+                            label,
+                            text_style(20., Color::WHITE),
+                        ));
+                    });
+            }
+        });
+    commands.insert_resource(CustomGameForm(options));
+}
+
+/// Applies the clicked steps to the form, holding shift steps by 10
+fn form_step_handling(
+    keys: Res<Input<KeyCode>>,
+    mut form: ResMut<CustomGameForm>,
+    steps: Query<(&Interaction, &FormStep), Changed<Interaction>>,
+) {
+    let factor = match keys.any_pressed([KeyCode::LShift, KeyCode::RShift]) {
+        true => 10,
+        false => 1,
+    };
+    for (interaction, step) in steps.iter() {
+        if *interaction == Interaction::Clicked {
+            step.field.apply(&mut form.0, step.delta * factor);
+        }
+    }
+}
+
+/// Refreshes the displayed values and validation messages
+fn update_form(
+    form: Res<CustomGameForm>,
+    mut values: Query<(&mut Text, &FormValue), Without<FormErrors>>,
+    mut errors: Query<&mut Text, With<FormErrors>>,
+) {
+    if !form.is_changed() {
+        return;
+    }
+    for (mut text, FormValue(field)) in values.iter_mut() {
+        text.sections[0].value = field.value(&form.0);
+    }
+    let messages = match form.0.validate() {
+        Ok(()) => String::new(),
+        Err(errors) => errors
+            .iter()
+            .map(ToString::to_string)
+            .collect::<Vec<_>>()
+            .join("\n"),
+    };
+    for mut text in errors.iter_mut() {
+        text.sections[0].value = messages.clone();
+    }
+}
+
+fn despawn_custom_game_dialog(
+    mut commands: Commands,
+    dialogs: Query<Entity, With<CustomGameRoot>>,
+) {
+    for entity in dialogs.iter() {
+        commands.entity(entity).despawn_recursive();
+    }
+    commands.remove_resource::<CustomGameForm>();
+}
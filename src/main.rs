@@ -7,11 +7,32 @@ use derive_more::IsVariant;
 #[cfg(feature = "debug")]
 use bevy_inspector_egui::quick::WorldInspectorPlugin;
 
-use board_plugin::{resource::BoardOptions, BoardPlugin};
+use board_plugin::{
+    bot::{RandomBot, SolverBot},
+    resource::{
+        BoardOptions, BotPlayer, DailyChallenge, EndlessMode, InputAction, InputBindings,
+        TileRendering,
+    },
+    BoardPlugin,
+};
+
+mod custom_game;
+mod menu;
+mod settings;
 
 #[derive(Debug, Clone, Eq, PartialEq, Hash, IsVariant)]
 pub enum AppState {
+    MainMenu,
+    DifficultySelect,
+    CustomGame,
+    Settings,
     InGame,
+    /// Pushed over `InGame`, the board is hidden and the game clock stopped
+    Paused,
+    /// Pushed over `InGame` when a mine exploded
+    GameOver,
+    /// Pushed over `InGame` when the board is completed
+    Victory,
     Reloading,
 }
 
@@ -38,14 +59,56 @@ fn main() {
     #[cfg(feature = "debug")]
     app.add_plugin(WorldInspectorPlugin);
 
-    app.add_state(AppState::InGame)
+    // Demo mode: `--bot` lets the solver bot play, `--bot=random` a random bot
+    let bot_arg = std::env::args().find(|arg| arg.starts_with("--bot"));
+    let mut initial_state = AppState::MainMenu;
+    match bot_arg.as_deref() {
+        Some("--bot=random") => {
+            app.insert_resource(BotPlayer::new(RandomBot::default(), 2.));
+        }
+        Some(_) => {
+            app.insert_resource(BotPlayer::new(SolverBot::new(), 5.));
+        }
+        None => (),
+    }
+    if bot_arg.is_some() {
+        initial_state = AppState::InGame;
+    }
+
+    // Daily challenge: the board of the day, the same for every player
+    if std::env::args().any(|arg| arg == "--daily") {
+        app.insert_resource(DailyChallenge::today());
+        initial_state = AppState::InGame;
+    }
+
+    // Endless world, resumed from the last session: `--endless`
+    if std::env::args().any(|arg| arg == "--endless") {
+        app.insert_resource(EndlessMode::default());
+        initial_state = AppState::InGame;
+    }
+
+    // Large boards render faster from a texture atlas: `--atlas`, or only the chunks in view:
+    // `--chunked`
+    let rendering = std::env::args()
+        .find_map(|arg| match arg.as_str() {
+            "--atlas" => Some(TileRendering::Atlas),
+            "--chunked" => Some(TileRendering::Chunked),
+            _ => None,
+        })
+        .unwrap_or_default();
+
+    app.add_state(initial_state)
         .add_plugin(BoardPlugin {
             running_state: AppState::InGame,
         })
+        .add_plugin(menu::MenuPlugin)
+        .add_plugin(custom_game::CustomGamePlugin)
+        .add_plugin(settings::SettingsPlugin)
         .insert_resource(BoardOptions {
             map_size: (20, 20),
             bomb_count: 40,
             tile_padding: 3.,
+            rendering,
             ..default()
         })
         .add_startup_system(camera_setup)
@@ -57,17 +120,51 @@ fn camera_setup(mut commands: Commands) {
     commands.spawn(Camera2dBundle::default());
 }
 
-fn state_handling(mut state: ResMut<State<AppState>>, key: Res<Input<KeyCode>>) {
+fn state_handling(
+    mut state: ResMut<State<AppState>>,
+    key: Res<Input<KeyCode>>,
+    gamepads: Res<Gamepads>,
+    gamepad_buttons: Res<Input<GamepadButton>>,
+    bindings: Res<InputBindings>,
+) {
     if state.current().is_reloading() {
         log::info!("Loading game");
         state.set(AppState::InGame).unwrap();
     }
 
-    if key.just_pressed(KeyCode::G) {
+    // The settings screen captures the inputs to rebind
+    if state.current().is_settings() {
+        return;
+    }
+    let just_pressed = |action| bindings.just_pressed(action, &key, &gamepads, &gamepad_buttons);
+
+    if just_pressed(InputAction::Pause) {
+        let res = match state.current() {
+            AppState::InGame => state.push(AppState::Paused),
+            AppState::Paused => state.pop(),
+            _ => Ok(()),
+        };
+        if let Err(e) = res {
+            log::error!("Failed to toggle the pause: {e}");
+        }
+    }
+
+    // Restarting starts a new game from any screen
+    if just_pressed(InputAction::Restart) {
         log::debug!("Reloading detected! Current state: {state:?}");
-        if state.current().is_in_game() {
-            log::info!("Reloading game");
-            state.set(AppState::Reloading).unwrap();
+        let res = match state.current() {
+            AppState::MainMenu | AppState::DifficultySelect | AppState::CustomGame => {
+                log::info!("Starting a new game");
+                state.set(AppState::InGame)
+            }
+            AppState::Reloading => Ok(()),
+            _ => {
+                log::info!("Reloading game");
+                state.replace(AppState::Reloading)
+            }
+        };
+        if let Err(e) = res {
+            log::error!("Failed to start a new game: {e}");
         }
     }
 }
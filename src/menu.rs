@@ -0,0 +1,306 @@
+use bevy::{app::AppExit, log, prelude::*};
+
+use board_plugin::{
+    event::{BoardCompletedEvent, GameOverEvent},
+    resource::{BoardOptions, DailyChallenge, EndlessMode},
+};
+
+use crate::{custom_game::CustomGameForm, AppState};
+
+pub(crate) const NORMAL_BUTTON: Color = Color::rgb(0.15, 0.15, 0.15);
+const HOVERED_BUTTON: Color = Color::rgb(0.25, 0.25, 0.25);
+const PRESSED_BUTTON: Color = Color::rgb(0.35, 0.75, 0.35);
+
+/// Menu screens of the game, built with Bevy UI
+pub struct MenuPlugin;
+
+/// Menu screen root marker component
+#[derive(Debug, Clone, Copy, Component)]
+struct MenuRoot;
+
+/// Action triggered by a menu button
+#[derive(Debug, Clone, Component)]
+pub(crate) enum MenuAction {
+    /// Opens the difficulty selection
+    Play,
+    /// Opens the settings screen
+    Settings,
+    /// Starts a game with the given options
+    Start(BoardOptions),
+    /// Starts the daily challenge
+    Daily,
+    /// Resumes the endless world, or starts a new one
+    Endless,
+    /// Opens the custom game dialog
+    Custom,
+    /// Starts a game with the custom game dialog options, if they are valid
+    StartCustom,
+    /// Resumes the paused game
+    Resume,
+    /// Starts a new game with the same options
+    Restart,
+    /// Goes back to the main menu
+    MainMenu,
+    /// Closes the game
+    Quit,
+}
+
+impl Plugin for MenuPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_system_set(SystemSet::on_enter(AppState::MainMenu).with_system(spawn_main_menu))
+            .add_system_set(SystemSet::on_exit(AppState::MainMenu).with_system(despawn_menu))
+            .add_system_set(
+                SystemSet::on_enter(AppState::DifficultySelect)
+                    .with_system(spawn_difficulty_select),
+            )
+            .add_system_set(
+                SystemSet::on_exit(AppState::DifficultySelect).with_system(despawn_menu),
+            )
+            .add_system_set(SystemSet::on_enter(AppState::Paused).with_system(spawn_pause_menu))
+            .add_system_set(SystemSet::on_exit(AppState::Paused).with_system(despawn_menu))
+            .add_system_set(
+                SystemSet::on_enter(AppState::GameOver).with_system(spawn_game_over_menu),
+            )
+            .add_system_set(SystemSet::on_exit(AppState::GameOver).with_system(despawn_menu))
+            .add_system_set(SystemSet::on_enter(AppState::Victory).with_system(spawn_victory_menu))
+            .add_system_set(SystemSet::on_exit(AppState::Victory).with_system(despawn_menu))
+            .add_system_set(SystemSet::on_update(AppState::InGame).with_system(game_end_handling))
+            .add_system(menu_button_handling);
+    }
+}
+
+/// Board presets of the difficulty selection
+fn difficulty_presets() -> [(&'static str, BoardOptions); 3] {
+    let preset = |map_size, bomb_count| BoardOptions {
+        map_size,
+        bomb_count,
+        tile_padding: 3.,
+        ..Default::default()
+    };
+    [
+        ("Beginner", preset((9, 9), 10)),
+        ("Intermediate", preset((16, 16), 40)),
+        ("Expert", preset((30, 16), 99)),
+    ]
+}
+
+fn spawn_main_menu(mut commands: Commands, asset_server: Res<AssetServer>) {
+    spawn_menu(
+        &mut commands,
+        &asset_server,
+        "Mine Sweeper",
+        vec![
+            ("Play", MenuAction::Play),
+            ("Settings", MenuAction::Settings),
+            ("Quit", MenuAction::Quit),
+        ],
+    );
+}
+
+fn spawn_difficulty_select(mut commands: Commands, asset_server: Res<AssetServer>) {
+    let mut buttons: Vec<_> = difficulty_presets()
+        .into_iter()
+        .map(|(label, options)| (label, MenuAction::Start(options)))
+        .collect();
+    buttons.push(("Custom", MenuAction::Custom));
+    buttons.push(("Daily challenge", MenuAction::Daily));
+    buttons.push(("Endless", MenuAction::Endless));
+    buttons.push(("Back", MenuAction::MainMenu));
+    spawn_menu(&mut commands, &asset_server, "Difficulty", buttons);
+}
+
+fn spawn_pause_menu(mut commands: Commands, asset_server: Res<AssetServer>) {
+    spawn_menu(
+        &mut commands,
+        &asset_server,
+        "Paused",
+        vec![
+            ("Resume", MenuAction::Resume),
+            ("Restart", MenuAction::Restart),
+            ("Custom game", MenuAction::Custom),
+            ("Main menu", MenuAction::MainMenu),
+        ],
+    );
+}
+
+fn spawn_game_over_menu(mut commands: Commands, asset_server: Res<AssetServer>) {
+    spawn_menu(
+        &mut commands,
+        &asset_server,
+        "Game over",
+        vec![
+            ("Play again", MenuAction::Restart),
+            ("Main menu", MenuAction::MainMenu),
+        ],
+    );
+}
+
+fn spawn_victory_menu(mut commands: Commands, asset_server: Res<AssetServer>) {
+    spawn_menu(
+        &mut commands,
+        &asset_server,
+        "Victory!",
+        vec![
+            ("Play again", MenuAction::Restart),
+            ("Main menu", MenuAction::MainMenu),
+        ],
+    );
+}
+
+/// Spawns a full screen menu with a title and a column of buttons
+fn spawn_menu(
+    commands: &mut Commands,
+    asset_server: &AssetServer,
+    title: &str,
+    buttons: Vec<(&str, MenuAction)>,
+) {
+    let font: Handle<Font> = asset_server.load("fonts/pixeled.ttf");
+    commands
+        .spawn(NodeBundle {
+            style: Style {
+                position_type: PositionType::Absolute,
+                size: Size::new(Val::Percent(100.), Val::Percent(100.)),
+                flex_direction: FlexDirection::Column,
+                justify_content: JustifyContent::Center,
+                align_items: AlignItems::Center,
+                ..Default::default()
+            },
+            background_color: Color::rgba(0., 0., 0., 0.7).into(),
+            z_index: ZIndex::Global(20),
+            ..Default::default()
+        })
+        .insert(Name::new("Menu"))
+        .insert(MenuRoot)
+        .with_children(|parent| {
+            parent.spawn(
+                TextBundle::from_section(
+                    title,
+                    TextStyle {
+                        font: font.clone(),
+                        font_size: 40.,
+                        color: Color::WHITE,
+                    },
+                )
+                .with_style(Style {
+                    margin: UiRect::all(Val::Px(20.)),
+                    ..Default::default()
+                }),
+            );
+            for (label, action) in buttons {
+                parent
+                    .spawn(ButtonBundle {
+                        style: Style {
+                            size: Size::new(Val::Px(300.), Val::Px(50.)),
+                            margin: UiRect::all(Val::Px(8.)),
+                            justify_content: JustifyContent::Center,
+                            align_items: AlignItems::Center,
+                            ..Default::default()
+                        },
+                        background_color: NORMAL_BUTTON.into(),
+                        ..Default::default()
+                    })
+                    .insert(action)
+                    .with_children(|button| {
+                        button.spawn(TextBundle::from_section(
+                            label,
+                            TextStyle {
+                                font: font.clone(),
+                                font_size: 20.,
+                                color: Color::WHITE,
+                            },
+                        ));
+                    });
+            }
+        });
+}
+
+fn despawn_menu(mut commands: Commands, menus: Query<Entity, With<MenuRoot>>) {
+    for entity in menus.iter() {
+        commands.entity(entity).despawn_recursive();
+    }
+}
+
+#[allow(clippy::type_complexity)]
+fn menu_button_handling(
+    mut commands: Commands,
+    mut state: ResMut<State<AppState>>,
+    mut exit_ewr: EventWriter<AppExit>,
+    custom_form: Option<Res<CustomGameForm>>,
+    mut buttons: Query<
+        (&Interaction, Option<&MenuAction>, &mut BackgroundColor),
+        (Changed<Interaction>, With<Button>),
+    >,
+) {
+    for (interaction, action, mut color) in buttons.iter_mut() {
+        *color = match interaction {
+            Interaction::Clicked => PRESSED_BUTTON,
+            Interaction::Hovered => HOVERED_BUTTON,
+            Interaction::None => NORMAL_BUTTON,
+        }
+        .into();
+        let (Interaction::Clicked, Some(action)) = (interaction, action) else {
+            continue;
+        };
+        log::debug!("Menu action: {action:?}");
+        let res = match action {
+            MenuAction::Play => state.set(AppState::DifficultySelect),
+            MenuAction::Settings => state.set(AppState::Settings),
+            MenuAction::Start(options) => {
+                commands.remove_resource::<DailyChallenge>();
+                commands.remove_resource::<EndlessMode>();
+                commands.insert_resource(options.clone());
+                state.set(AppState::InGame)
+            }
+            MenuAction::Daily => {
+                commands.remove_resource::<EndlessMode>();
+                commands.insert_resource(DailyChallenge::today());
+                state.set(AppState::InGame)
+            }
+            MenuAction::Endless => {
+                commands.remove_resource::<DailyChallenge>();
+                commands.insert_resource(EndlessMode::default());
+                state.set(AppState::InGame)
+            }
+            // Replacing the state stack leaves the paused game, if any
+            MenuAction::Custom => state.replace(AppState::CustomGame),
+            MenuAction::StartCustom => match custom_form.as_deref() {
+                Some(CustomGameForm(options)) if options.validate().is_ok() => {
+                    commands.remove_resource::<DailyChallenge>();
+                    commands.remove_resource::<EndlessMode>();
+                    commands.insert_resource(options.clone());
+                    state.set(AppState::InGame)
+                }
+                _ => Ok(()),
+            },
+            MenuAction::Resume => state.pop(),
+            // Replacing the state stack exits the game state, cleaning up the board
+            MenuAction::Restart => state.replace(AppState::Reloading),
+            MenuAction::MainMenu => state.replace(AppState::MainMenu),
+            MenuAction::Quit => {
+                exit_ewr.send(AppExit);
+                Ok(())
+            }
+        };
+        if let Err(e) = res {
+            log::error!("Failed to apply menu action {action:?}: {e}");
+        }
+    }
+}
+
+/// Displays the game over or victory screen over the finished board
+fn game_end_handling(
+    mut state: ResMut<State<AppState>>,
+    mut board_completed_evr: EventReader<BoardCompletedEvent>,
+    mut game_over_evr: EventReader<GameOverEvent>,
+) {
+    let completed = board_completed_evr.iter().count() > 0;
+    let lost = game_over_evr.iter().count() > 0;
+    let next = match (completed, lost) {
+        (true, _) => AppState::Victory,
+        (_, true) => AppState::GameOver,
+        _ => return,
+    };
+    if let Err(e) = state.push(next) {
+        log::error!("Failed to end the game: {e}");
+    }
+}
@@ -0,0 +1,253 @@
+use bevy::{log, prelude::*};
+
+use board_plugin::resource::{InputAction, InputBindings};
+
+use crate::{
+    menu::{MenuAction, NORMAL_BUTTON},
+    AppState,
+};
+
+/// Settings screen, rebinding the input actions
+pub struct SettingsPlugin;
+
+/// Settings screen root marker component
+#[derive(Debug, Clone, Copy, Component)]
+struct SettingsRoot;
+
+/// Action of a settings button
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Component)]
+enum SettingsAction {
+    /// Waits for the next input to bind it to the action
+    Rebind(InputAction),
+    /// Swaps the reveal and flag mouse buttons
+    SwapMouseButtons,
+    /// Restores the default bindings
+    Reset,
+}
+
+/// Displayed bindings of an action
+#[derive(Debug, Clone, Copy, Component)]
+struct BindingText(InputAction);
+
+/// Action waiting for its new input, if any
+#[derive(Debug, Clone, Copy, Default, Resource)]
+struct Rebinding(Option<InputAction>);
+
+impl Plugin for SettingsPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<Rebinding>()
+            .add_system_set(SystemSet::on_enter(AppState::Settings).with_system(spawn_settings))
+            .add_system_set(
+                SystemSet::on_update(AppState::Settings)
+                    .with_system(capture_binding.before(settings_button_handling))
+                    .with_system(settings_button_handling)
+                    .with_system(update_binding_texts.after(settings_button_handling)),
+            )
+            .add_system_set(SystemSet::on_exit(AppState::Settings).with_system(despawn_settings));
+    }
+}
+
+/// Human readable bindings of an action
+fn binding_label(bindings: &InputBindings, action: InputAction) -> String {
+    let binding = bindings.get(action);
+    let inputs: Vec<String> = binding
+        .keys
+        .iter()
+        .map(|k| format!("{k:?}"))
+        .chain(binding.mouse_buttons.iter().map(|b| format!("Mouse {b:?}")))
+        .chain(binding.gamepad_buttons.iter().map(|b| format!("Pad {b:?}")))
+        .collect();
+    match inputs.is_empty() {
+        true => "Unbound".to_string(),
+        false => inputs.join(" / "),
+    }
+}
+
+fn spawn_settings(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    bindings: Res<InputBindings>,
+) {
+    let font: Handle<Font> = asset_server.load("fonts/pixeled.ttf");
+    let text_style = |font_size| TextStyle {
+        font: font.clone(),
+        font_size,
+        color: Color::WHITE,
+    };
+    let button = |width| ButtonBundle {
+        style: Style {
+            size: Size::new(Val::Px(width), Val::Px(40.)),
+            margin: UiRect::all(Val::Px(4.)),
+            justify_content: JustifyContent::Center,
+            align_items: AlignItems::Center,
+            ..Default::default()
+        },
+        background_color: NORMAL_BUTTON.into(),
+        ..Default::default()
+    };
+
+    commands
+        .spawn(NodeBundle {
+            style: Style {
+                position_type: PositionType::Absolute,
+                size: Size::new(Val::Percent(100.), Val::Percent(100.)),
+                flex_direction: FlexDirection::Column,
+                justify_content: JustifyContent::Center,
+                align_items: AlignItems::Center,
+                ..Default::default()
+            },
+            background_color: Color::rgba(0., 0., 0., 0.7).into(),
+            z_index: ZIndex::Global(20),
+            ..Default::default()
+        })
+        .insert(Name::new("Settings"))
+        .insert(SettingsRoot)
+        .with_children(|parent| {
+            parent.spawn(
+                TextBundle::from_section("Settings", text_style(40.)).with_style(Style {
+                    margin: UiRect::all(Val::Px(20.)),
+                    ..Default::default()
+                }),
+            );
+            for action in InputAction::ALL {
+                parent
+                    .spawn(NodeBundle {
+                        style: Style {
+                            align_items: AlignItems::Center,
+                            ..Default::default()
+                        },
+                        ..Default::default()
+                    })
+                    .with_children(|row| {
+                        row.spawn(
+                            TextBundle::from_section(action.label(), text_style(16.)).with_style(
+                                Style {
+                                    size: Size::new(Val::Px(150.), Val::Auto),
+                                    ..Default::default()
+                                },
+                            ),
+                        );
+                        row.spawn((button(450.), SettingsAction::Rebind(action)))
+                            .with_children(|b| {
+                                b.spawn((
+                                    TextBundle::from_section(
+                                        binding_label(&bindings, action),
+                                        text_style(14.),
+                                    ),
+                                    BindingText(action),
+                                ));
+                            });
+                    });
+            }
+            let buttons = [
+                ("Swap mouse buttons", SettingsAction::SwapMouseButtons),
+                ("Reset defaults", SettingsAction::Reset),
+            ];
+            for (label, action) in buttons {
+                parent.spawn((button(300.), action)).with_children(|b| {
+                    b.spawn(TextBundle::from_section(label, text_style(20.)));
+                });
+            }
+            parent
+                .spawn((button(300.), MenuAction::MainMenu))
+                .with_children(|b| {
+                    b.spawn(TextBundle::from_section("Back", text_style(20.)));
+                });
+        });
+}
+
+/// Binds the next pressed key, mouse button or gamepad button to the action being rebound,
+/// replacing its previous input of the same device. The input is taken from the other actions,
+/// the cursor movement and board shortcut inputs can't be bound
+fn capture_binding(
+    mut rebinding: ResMut<Rebinding>,
+    mut bindings: ResMut<InputBindings>,
+    keys: Res<Input<KeyCode>>,
+    mouse_buttons: Res<Input<MouseButton>>,
+    gamepad_buttons: Res<Input<GamepadButton>>,
+    mut texts: Query<(&mut Text, &BindingText)>,
+) {
+    let Some(action) = rebinding.0 else {
+        return;
+    };
+    let mut binding = bindings.get(action).clone();
+    let reserved = if let Some(key) = keys.get_just_pressed().next() {
+        binding.keys = vec![*key];
+        InputBindings::is_reserved_key(*key)
+    } else if let Some(button) = mouse_buttons.get_just_pressed().next() {
+        binding.mouse_buttons = vec![*button];
+        false
+    } else if let Some(button) = gamepad_buttons.get_just_pressed().next() {
+        binding.gamepad_buttons = vec![button.button_type];
+        InputBindings::is_cursor_gamepad_button(button.button_type)
+    } else {
+        return;
+    };
+    // Waits for another input
+    if reserved {
+        for (mut text, BindingText(a)) in texts.iter_mut() {
+            if *a == action {
+                text.sections[0].value = "Reserved, press another input...".to_string();
+            }
+        }
+        return;
+    }
+    log::info!("Rebound {action:?} to {binding:?}");
+    for other in bindings.set(action, binding) {
+        log::info!("Input taken from {other:?}");
+    }
+    rebinding.0 = None;
+    if let Err(e) = bindings.save() {
+        log::error!("Failed to save input bindings: {e}");
+    }
+}
+
+fn settings_button_handling(
+    mut rebinding: ResMut<Rebinding>,
+    mut bindings: ResMut<InputBindings>,
+    buttons: Query<(&Interaction, &SettingsAction), Changed<Interaction>>,
+    mut texts: Query<(&mut Text, &BindingText)>,
+) {
+    for (interaction, action) in buttons.iter() {
+        if *interaction != Interaction::Clicked {
+            continue;
+        }
+        match action {
+            SettingsAction::Rebind(action) => {
+                rebinding.0 = Some(*action);
+                for (mut text, BindingText(a)) in texts.iter_mut() {
+                    if a == action {
+                        text.sections[0].value = "Press an input...".to_string();
+                    }
+                }
+                continue;
+            }
+            SettingsAction::SwapMouseButtons => bindings.swap_mouse_buttons(),
+            SettingsAction::Reset => bindings.reset(),
+        }
+        rebinding.0 = None;
+        if let Err(e) = bindings.save() {
+            log::error!("Failed to save input bindings: {e}");
+        }
+    }
+}
+
+fn update_binding_texts(bindings: Res<InputBindings>, mut texts: Query<(&mut Text, &BindingText)>) {
+    if !bindings.is_changed() {
+        return;
+    }
+    for (mut text, BindingText(action)) in texts.iter_mut() {
+        text.sections[0].value = binding_label(&bindings, *action);
+    }
+}
+
+fn despawn_settings(
+    mut commands: Commands,
+    mut rebinding: ResMut<Rebinding>,
+    screens: Query<Entity, With<SettingsRoot>>,
+) {
+    for entity in screens.iter() {
+        commands.entity(entity).despawn_recursive();
+    }
+    rebinding.0 = None;
+}